@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use libpassgen::{generate_ascii_password, generate_password, generate_password_into, Pool};
+
+fn ascii_pool() -> Pool {
+    let mut pool = Pool::alphanumeric();
+    pool.extend_from_string("!#$%&*+-=?");
+
+    pool
+}
+
+fn generate(c: &mut Criterion) {
+    let pool = ascii_pool();
+    assert_eq!(pool.len(), 72);
+
+    c.bench_function("generate_password 72 chars pool, length 64", |b| {
+        b.iter(|| generate_password(black_box(&pool), black_box(64)))
+    });
+    let digits = Pool::digits();
+    c.bench_function("generate_password 10 chars pool, length 64", |b| {
+        b.iter(|| generate_password(black_box(&digits), black_box(64)))
+    });
+    c.bench_function("generate_ascii_password 72 chars pool, length 64", |b| {
+        b.iter(|| generate_ascii_password(black_box(&pool), black_box(64)))
+    });
+
+    let mut buf = String::new();
+    c.bench_function("generate_password_into 72 chars pool, length 64", |b| {
+        b.iter(|| generate_password_into(black_box(&pool), black_box(64), &mut buf))
+    });
+}
+
+criterion_group!(benches, generate);
+criterion_main!(benches);
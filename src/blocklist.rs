@@ -0,0 +1,306 @@
+use crate::{sample_password, validate, PassgenError, Pool};
+use alloc::string::String;
+use rand::Rng;
+
+/// Number of candidates drawn by [`generate_avoiding`] and
+/// [`generate_with_policy`](crate::generate_with_policy) before giving up with
+/// [`PassgenError::MaxAttemptsExceeded`]. Change it with [`generate_avoiding_with_attempts`]
+/// or [`Policy::max_attempts`](crate::Policy::max_attempts), and pass it to
+/// [`generate_until`], which always takes an explicit limit.
+///
+/// A constraint which rejects most candidates makes success within this many attempts
+/// unlikely, and is better expressed another way, for example by removing chars from the pool.
+pub const DEFAULT_MAX_ATTEMPTS: usize = 10_000;
+
+/// Generate random password of `length` chars picked from `pool` which contains none of the
+/// `forbidden` substrings, such as `"123"` or `"password"`. Empty substrings are ignored.
+///
+/// Candidates are drawn and rejected until one is accepted, up to [`DEFAULT_MAX_ATTEMPTS`]
+/// times. Rejecting candidates doesn't change which of the accepted passwords is picked, but
+/// it lowers the entropy by the share of rejected ones.
+///
+/// Each candidate is compared with every forbidden substring, so a check costs about
+/// `length * forbidden.len()` comparisons. The number of attempts is what matters more: it
+/// grows with the probability that a random candidate contains a forbidden substring, which
+/// is high for many or short substrings of chars frequent in the pool, and for long passwords.
+/// A single char substring simply removes that char from the pool, so prefer
+/// [`Pool::remove_all`] for those.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_avoiding};
+/// let password = generate_avoiding(&Pool::digits(), 8, &["123", "000"]).unwrap();
+///
+/// assert_eq!(password.len(), 8);
+/// assert!(!password.contains("123") && !password.contains("000"));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero and [`PassgenError::MaxAttemptsExceeded`] if no candidate was accepted.
+#[cfg(feature = "std")]
+pub fn generate_avoiding(
+    pool: &Pool,
+    length: usize,
+    forbidden: &[&str],
+) -> Result<String, PassgenError> {
+    generate_avoiding_with_rng(pool, length, forbidden, &mut rand::thread_rng())
+}
+
+/// Generate random password avoiding the `forbidden` substrings using the given random number
+/// generator. See [`generate_avoiding`].
+///
+/// # Errors
+/// Same as [`generate_avoiding`].
+pub fn generate_avoiding_with_rng<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    forbidden: &[&str],
+    rng: &mut R,
+) -> Result<String, PassgenError> {
+    generate_avoiding_with_attempts(pool, length, forbidden, DEFAULT_MAX_ATTEMPTS, rng)
+}
+
+/// Generate random password avoiding the `forbidden` substrings, drawing at most
+/// `max_attempts` candidates instead of [`DEFAULT_MAX_ATTEMPTS`]. See [`generate_avoiding`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{PassgenError, Pool, generate_avoiding_with_attempts};
+/// let pool: Pool = "ab".parse().unwrap();
+/// let mut rng = rand::thread_rng();
+/// let result = generate_avoiding_with_attempts(&pool, 8, &["a", "b"], 50, &mut rng);
+///
+/// assert_eq!(result, Err(PassgenError::MaxAttemptsExceeded { attempts: 50 }));
+/// # }
+/// ```
+///
+/// # Errors
+/// Same as [`generate_avoiding`]. Zero attempts always return
+/// [`PassgenError::MaxAttemptsExceeded`].
+pub fn generate_avoiding_with_attempts<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    forbidden: &[&str],
+    max_attempts: usize,
+    rng: &mut R,
+) -> Result<String, PassgenError> {
+    generate_until_with_rng(
+        pool,
+        length,
+        |password| !contains_any(password, forbidden),
+        max_attempts,
+        rng,
+    )
+}
+
+/// Generate random password of `length` chars picked from `pool` for which `predicate`
+/// returns true, drawing at most `max_attempts` candidates, instead of looping until one is
+/// accepted. [`DEFAULT_MAX_ATTEMPTS`] is a reasonable limit.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{DEFAULT_MAX_ATTEMPTS, Pool, generate_until};
+/// let password = generate_until(
+///     &Pool::lowercase(),
+///     8,
+///     |password| password.starts_with(|ch| "aeiou".contains(ch)),
+///     DEFAULT_MAX_ATTEMPTS,
+/// )
+/// .unwrap();
+///
+/// assert!(password.starts_with(|ch| "aeiou".contains(ch)));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero and [`PassgenError::MaxAttemptsExceeded`], with `max_attempts`, if
+/// `predicate` rejected every candidate.
+#[cfg(feature = "std")]
+pub fn generate_until<F: FnMut(&str) -> bool>(
+    pool: &Pool,
+    length: usize,
+    predicate: F,
+    max_attempts: usize,
+) -> Result<String, PassgenError> {
+    generate_until_with_rng(
+        pool,
+        length,
+        predicate,
+        max_attempts,
+        &mut rand::thread_rng(),
+    )
+}
+
+/// Generate random password accepted by `predicate` using the given random number generator.
+/// See [`generate_until`].
+///
+/// # Errors
+/// Same as [`generate_until`].
+pub fn generate_until_with_rng<F: FnMut(&str) -> bool, R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    predicate: F,
+    max_attempts: usize,
+    rng: &mut R,
+) -> Result<String, PassgenError> {
+    validate(pool, length)?;
+
+    retry(
+        max_attempts,
+        || sample_password(pool, length, rng),
+        predicate,
+    )
+    .ok_or(PassgenError::MaxAttemptsExceeded {
+        attempts: max_attempts,
+    })
+}
+
+/// Returns the first of at most `max_attempts` candidates accepted by `predicate`
+pub(crate) fn retry<C, F>(max_attempts: usize, mut candidate: C, mut predicate: F) -> Option<String>
+where
+    C: FnMut() -> String,
+    F: FnMut(&str) -> bool,
+{
+    (0..max_attempts)
+        .map(|_| candidate())
+        .find(|password| predicate(password))
+}
+
+fn contains_any(password: &str, forbidden: &[&str]) -> bool {
+    forbidden
+        .iter()
+        .any(|word| !word.is_empty() && password.contains(word))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generate_avoiding_never_contains_forbidden() {
+        let pool: Pool = "abc".parse().unwrap();
+
+        for _ in 0..200 {
+            let password = generate_avoiding(&pool, 6, &["ab", "cc"]).unwrap();
+
+            assert_eq!(password.len(), 6);
+            assert!(!password.contains("ab") && !password.contains("cc"));
+        }
+    }
+
+    #[test]
+    fn generate_avoiding_ignores_empty_substrings() {
+        assert!(generate_avoiding(&Pool::digits(), 4, &[""]).is_ok());
+        assert!(generate_avoiding(&Pool::digits(), 4, &[]).is_ok());
+    }
+
+    #[test]
+    fn generate_avoiding_with_attempts_limit() {
+        let pool: Pool = "ab".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(
+            generate_avoiding_with_attempts(&pool, 3, &["a", "b"], 7, &mut rng),
+            Err(PassgenError::MaxAttemptsExceeded { attempts: 7 })
+        );
+        assert_eq!(
+            generate_avoiding_with_attempts(&pool, 3, &[], 0, &mut rng),
+            Err(PassgenError::MaxAttemptsExceeded { attempts: 0 })
+        );
+        assert_eq!(
+            generate_avoiding_with_attempts(&pool, 3, &["a"], 1_000, &mut rng),
+            Ok(String::from("bbb"))
+        );
+    }
+
+    #[test]
+    fn generate_until_satisfiable_predicate() {
+        let mut attempts = 0;
+        let password = generate_until(
+            &Pool::digits(),
+            4,
+            |password| {
+                attempts += 1;
+                password.starts_with('7')
+            },
+            DEFAULT_MAX_ATTEMPTS,
+        )
+        .unwrap();
+
+        assert!(password.starts_with('7'));
+        assert!(attempts < 200);
+    }
+
+    #[test]
+    fn generate_until_impossible_predicate() {
+        let mut attempts = 0;
+        let result = generate_until(
+            &Pool::digits(),
+            4,
+            |password| {
+                attempts += 1;
+                password.contains('a')
+            },
+            500,
+        );
+
+        assert_eq!(
+            result,
+            Err(PassgenError::MaxAttemptsExceeded { attempts: 500 })
+        );
+        assert_eq!(attempts, 500);
+    }
+
+    #[test]
+    fn generate_until_with_rng_errors() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(
+            generate_until_with_rng(&Pool::new(), 4, |_| true, 10, &mut rng),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generate_until_with_rng(&Pool::digits(), 0, |_| true, 10, &mut rng),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+
+    #[test]
+    fn generate_avoiding_impossible_constraint() {
+        let pool: Pool = "ab".parse().unwrap();
+
+        assert_eq!(
+            generate_avoiding(&pool, 3, &["a", "b"]),
+            Err(PassgenError::MaxAttemptsExceeded {
+                attempts: DEFAULT_MAX_ATTEMPTS
+            })
+        );
+    }
+
+    #[test]
+    fn generate_avoiding_with_rng_same_seed() {
+        let pool = Pool::alphanumeric();
+
+        assert_eq!(
+            generate_avoiding_with_rng(&pool, 12, &["a"], &mut StdRng::seed_from_u64(7)),
+            generate_avoiding_with_rng(&pool, 12, &["a"], &mut StdRng::seed_from_u64(7))
+        );
+    }
+
+    #[test]
+    fn generate_avoiding_errors() {
+        assert_eq!(
+            generate_avoiding(&Pool::new(), 4, &["a"]),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generate_avoiding(&Pool::digits(), 0, &["a"]),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+}
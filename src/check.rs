@@ -0,0 +1,111 @@
+use crate::{PassgenError, Pool};
+use alloc::string::String;
+
+/// Append a check char to `password`, computed from its chars and picked from `pool`, so
+/// that [`verify_check_char`] detects most transcription errors.
+///
+/// The check char is the char of `pool` at index `sum((i + 1) * code_point(c_i)) % pool.len()`.
+/// Weighting each code point by its position also catches most swaps of two chars. It only
+/// depends on the password and on the order of `pool`, and adds no security: anyone can
+/// compute it.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, append_check_char, verify_check_char};
+/// let pool = Pool::alphanumeric();
+/// let password = append_check_char("hunter2", &pool);
+///
+/// assert_eq!(password.chars().count(), 8);
+/// assert!(verify_check_char(&password, &pool));
+/// ```
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn append_check_char(password: &str, pool: &Pool) -> String {
+    if pool.is_empty() {
+        panic!("{}", PassgenError::EmptyPool);
+    }
+
+    let mut checked = String::with_capacity(password.len() + 4);
+    checked.push_str(password);
+    checked.push(check_char(password, pool));
+
+    checked
+}
+
+/// Returns true if the last char of `password` is the check char of the chars before it,
+/// as appended by [`append_check_char`] with the same `pool`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, append_check_char, verify_check_char};
+/// let pool = Pool::alphanumeric();
+/// let password = append_check_char("hunter2", &pool);
+///
+/// assert!(!verify_check_char(&password.replace('u', "v"), &pool));
+/// ```
+pub fn verify_check_char(password: &str, pool: &Pool) -> bool {
+    let mut chars = password.chars();
+    match chars.next_back() {
+        Some(last) if !pool.is_empty() => check_char(chars.as_str(), pool) == last,
+        _ => false,
+    }
+}
+
+fn check_char(password: &str, pool: &Pool) -> char {
+    let len = pool.len() as u64;
+    let sum = password.chars().enumerate().fold(0, |sum, (i, ch)| {
+        (sum + (i as u64 + 1) * u64::from(ch)) % len
+    });
+
+    *pool.get(sum as usize).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_check_char_is_deterministic() {
+        let pool = Pool::digits();
+
+        assert_eq!(
+            append_check_char("abc", &pool),
+            append_check_char("abc", &pool)
+        );
+        // 1 * 97 + 2 * 98 + 3 * 99 = 590
+        assert_eq!(append_check_char("abc", &pool), "abc0");
+    }
+
+    #[test]
+    fn verify_check_char_round_trip() {
+        let pool = Pool::alphanumeric();
+
+        for password in ["", "a", "correct horse", "pässwörd"] {
+            assert!(verify_check_char(
+                &append_check_char(password, &pool),
+                &pool
+            ));
+        }
+    }
+
+    #[test]
+    fn verify_check_char_detects_swap() {
+        let pool = Pool::alphanumeric();
+        let password = append_check_char("abcdef", &pool);
+
+        assert!(!verify_check_char(&password.replace("cd", "dc"), &pool));
+    }
+
+    #[test]
+    fn verify_check_char_edge_cases() {
+        assert!(!verify_check_char("", &Pool::digits()));
+        assert!(!verify_check_char("abc0", &Pool::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn append_check_char_passed_empty_pool() {
+        append_check_char("abc", &Pool::new());
+    }
+}
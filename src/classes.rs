@@ -0,0 +1,100 @@
+use crate::Pool;
+use core::fmt;
+
+/// Number of chars of each class in a [`Pool`], returned by [`Pool::class_counts`]. Every
+/// char is counted in exactly one class.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct ClassCounts {
+    /// ASCII lowercase letters
+    pub lowercase: usize,
+    /// ASCII uppercase letters
+    pub uppercase: usize,
+    /// ASCII digits
+    pub digits: usize,
+    /// ASCII punctuation, as in [`Pool::ascii_symbols`]
+    pub symbols: usize,
+    /// Whitespace chars, ASCII or not
+    pub whitespace: usize,
+    /// Every other char: non-ASCII letters, digits and symbols, and control chars
+    pub other: usize,
+}
+
+impl fmt::Display for ClassCounts {
+    /// Writes a summary like `26 lowercase, 0 uppercase, 10 digits, 0 symbols, 0 whitespace,
+    /// 0 other`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} lowercase, {} uppercase, {} digits, {} symbols, {} whitespace, {} other",
+            self.lowercase, self.uppercase, self.digits, self.symbols, self.whitespace, self.other
+        )
+    }
+}
+
+impl Pool {
+    /// Count the chars of the pool in each class, for example to describe a pool in a user
+    /// interface.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let counts = Pool::alphanumeric().class_counts();
+    ///
+    /// assert_eq!(counts.digits, 10);
+    /// assert_eq!(
+    ///     counts.to_string(),
+    ///     "26 lowercase, 26 uppercase, 10 digits, 0 symbols, 0 whitespace, 0 other"
+    /// );
+    /// ```
+    pub fn class_counts(&self) -> ClassCounts {
+        self.iter().fold(ClassCounts::default(), |mut counts, ch| {
+            match ch {
+                'a'..='z' => counts.lowercase += 1,
+                'A'..='Z' => counts.uppercase += 1,
+                '0'..='9' => counts.digits += 1,
+                ch if ch.is_ascii_punctuation() => counts.symbols += 1,
+                ch if ch.is_whitespace() => counts.whitespace += 1,
+                _ => counts.other += 1,
+            }
+
+            counts
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_counts_mixed_pool() {
+        let pool: Pool = "abcXY0123!? \u{430}\u{e9}\t".parse().unwrap();
+        let counts = pool.class_counts();
+
+        assert_eq!(
+            counts,
+            ClassCounts {
+                lowercase: 3,
+                uppercase: 2,
+                digits: 4,
+                symbols: 2,
+                whitespace: 2,
+                other: 2,
+            }
+        );
+        assert_eq!(
+            counts.to_string(),
+            "3 lowercase, 2 uppercase, 4 digits, 2 symbols, 2 whitespace, 2 other"
+        );
+    }
+
+    #[test]
+    fn class_counts_presets() {
+        assert_eq!(Pool::ascii_symbols().class_counts().symbols, 32);
+        assert_eq!(Pool::new().class_counts(), ClassCounts::default());
+        assert_eq!(
+            Pool::lowercase().count_matching(|ch| "aeiou".contains(ch)),
+            5
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{unwrap_or_empty, validate, Pool};
+use core::hint::black_box;
+use rand::Rng;
+
+/// Generate random password doing the same work for every char, whatever the char picked.
+///
+/// This is a best-effort mitigation of timing side channels, **not** a constant-time
+/// guarantee:
+/// - each char consumes exactly one `u64` from the generator, reduced to an index with a
+///   widening multiplication instead of a rejection loop. The bias this leaves is below
+///   `pool.len() / 2^64` and can't be measured
+/// - the char is selected by reading every char of the pool and masking, so memory accesses
+///   don't depend on the index
+///
+/// The compiler, the CPU and the random number generator itself may still introduce
+/// data-dependent timing, and pushing a char to the `String` depends on its UTF-8 length.
+/// With an ASCII pool every char has the same length. The cost is `O(length * pool.len())`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_ct};
+/// let password = generate_password_ct(&Pool::full(), 24);
+///
+/// assert_eq!(password.len(), 24);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_ct(pool: &Pool, length: usize) -> String {
+    unwrap_or_empty(validate(pool, length));
+
+    sample_ct(pool, length, &mut rand::thread_rng())
+}
+
+fn sample_ct<R: Rng + ?Sized>(pool: &Pool, length: usize, rng: &mut R) -> String {
+    let chars: Vec<u32> = pool.iter().map(|ch| u32::from(*ch)).collect();
+    let mut password = String::with_capacity(crate::max_char_len(pool) * length);
+
+    for _ in 0..length {
+        let idx = ((u128::from(rng.next_u64()) * chars.len() as u128) >> 64) as u64;
+        let mut selected = 0u32;
+        for (j, ch) in chars.iter().enumerate() {
+            // All ones when j == idx, zero otherwise, without branching
+            let is_selected = ((j as u64 ^ idx).wrapping_sub(1) >> 63) as u32;
+            selected |= ch & black_box(is_selected.wrapping_neg());
+        }
+        password.push(char::from_u32(selected).unwrap());
+    }
+
+    password
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn generate_password_ct_assert_len() {
+        let pool = Pool::alphanumeric();
+        let password = generate_password_ct(&pool, 32);
+
+        assert_eq!(password.chars().count(), 32);
+        assert!(password.chars().all(|ch| pool.contains(ch)));
+    }
+
+    #[test]
+    fn generate_password_ct_uses_whole_pool() {
+        let pool: Pool = "aé€😀".parse().unwrap();
+        let password = generate_password_ct(&pool, 400);
+
+        assert!(pool.iter().all(|ch| password.contains(*ch)));
+    }
+
+    #[test]
+    fn sample_ct_maps_range_evenly() {
+        let pool = Pool::digits();
+        // Steps of 2^64 / 10 walk through every index in order
+        let mut rng = StepRng::new(0, u64::MAX / 10 + 1);
+
+        assert_eq!(sample_ct(&pool, 10, &mut rng), "0123456789");
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_ct_passed_empty_pool() {
+        generate_password_ct(&Pool::new(), 8);
+    }
+
+    #[test]
+    fn generate_password_ct_zero_length() {
+        assert_eq!(generate_password_ct(&Pool::digits(), 0), "");
+    }
+}
@@ -0,0 +1,207 @@
+//! Reproducible password generation from a fixed seed.
+//!
+//! **This module is meant for tests and snapshots, not for production passwords.** Anyone who
+//! knows the seed can regenerate the exact same passwords.
+//!
+//! The output only depends on the seed, the pool (including its order) and the length: it is
+//! identical across platforms, pointer widths and endianness.
+
+use crate::{unwrap_or_empty, validate, Pool};
+use alloc::string::String;
+use alloc::vec::Vec;
+use rand::Rng;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+
+/// Generate a password from `seed` using [`ChaCha20Rng`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, deterministic::generate_password_seeded};
+/// let pool = "0123456789".parse().unwrap();
+///
+/// assert_eq!(
+///     generate_password_seeded(&pool, 15, [7; 32]),
+///     generate_password_seeded(&pool, 15, [7; 32])
+/// );
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_seeded(pool: &Pool, length: usize, seed: [u8; 32]) -> String {
+    unwrap_or_empty(validate(pool, length));
+
+    sample_portable(pool, length, &mut ChaCha20Rng::from_seed(seed))
+}
+
+/// Generate a password from a seed of any length, such as a master phrase followed by a site
+/// name, so the same inputs always give back the same password.
+///
+/// The [`ChaCha20Rng`] seed is the SHA-256 hash of `seed`, then chars are picked as in
+/// [`generate_password_seeded`]. The output only depends on `seed`, the pool (including its
+/// order) and the length.
+///
+/// **This is not a substitute for a real password manager.** SHA-256 is fast and unsalted,
+/// so anyone who gets one of the passwords can try master phrases offline at a very high
+/// rate, and a weak or reused phrase exposes every password derived from it. Changing one
+/// password also means changing the seed.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, deterministic::generate_password_from_seed};
+/// let pool = Pool::alphanumeric();
+/// let password = generate_password_from_seed(&pool, 20, b"correct horse:example.com");
+///
+/// assert_eq!(
+///     password,
+///     generate_password_from_seed(&pool, 20, b"correct horse:example.com")
+/// );
+/// assert_ne!(
+///     password,
+///     generate_password_from_seed(&pool, 20, b"correct horse:example.org")
+/// );
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_from_seed(pool: &Pool, length: usize, seed: &[u8]) -> String {
+    generate_password_seeded(pool, length, Sha256::digest(seed).into())
+}
+
+/// Generate `count` passwords from `seed` using a single [`ChaCha20Rng`] stream.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, deterministic::generate_n_passwords_seeded};
+/// let pool = "0123456789".parse().unwrap();
+/// let vec_passwords = generate_n_passwords_seeded(&pool, 15, 5, [7; 32]);
+///
+/// assert_eq!(vec_passwords.len(), 5);
+/// ```
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_n_passwords_seeded(
+    pool: &Pool,
+    length: usize,
+    count: usize,
+    seed: [u8; 32],
+) -> Vec<String> {
+    unwrap_or_empty(validate(pool, length));
+
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    (0..count)
+        .map(|_| sample_portable(pool, length, &mut rng))
+        .collect()
+}
+
+/// Sample indices as `u32` so the result does not depend on the width of `usize`
+fn sample_portable(pool: &Pool, length: usize, rng: &mut ChaCha20Rng) -> String {
+    let len = pool.len() as u32;
+
+    (0..length)
+        .map(|_| *pool.get(rng.gen_range(0..len) as usize).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_password_seeded_known_output() {
+        let pool = Pool::alphanumeric();
+
+        assert_eq!(
+            generate_password_seeded(&pool, 20, [42; 32]),
+            "fKDgZExCI6AzTYavhmWn"
+        );
+    }
+
+    #[test]
+    fn generate_password_seeded_same_seed() {
+        let pool = Pool::alphanumeric();
+
+        assert_eq!(
+            generate_password_seeded(&pool, 32, [1; 32]),
+            generate_password_seeded(&pool, 32, [1; 32])
+        );
+    }
+
+    #[test]
+    fn generate_password_seeded_different_seed() {
+        let pool = Pool::alphanumeric();
+
+        assert_ne!(
+            generate_password_seeded(&pool, 32, [1; 32]),
+            generate_password_seeded(&pool, 32, [2; 32])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_seeded_passed_empty_pool() {
+        generate_password_seeded(&Pool::new(), 15, [0; 32]);
+    }
+
+    #[test]
+    fn generate_password_seeded_zero_length() {
+        assert_eq!(generate_password_seeded(&Pool::digits(), 0, [0; 32]), "");
+        assert_eq!(
+            generate_n_passwords_seeded(&Pool::digits(), 0, 3, [0; 32]),
+            vec![""; 3]
+        );
+    }
+
+    #[test]
+    fn generate_password_from_seed_known_output() {
+        let pool = Pool::alphanumeric();
+        let password = generate_password_from_seed(&pool, 20, b"master phrase");
+
+        assert_eq!(password, "sq1Bn3AnUQUA6CuzrCcf");
+        assert_eq!(
+            password,
+            generate_password_from_seed(&pool, 20, b"master phrase")
+        );
+        assert_ne!(
+            password,
+            generate_password_from_seed(&pool, 20, b"Master phrase")
+        );
+    }
+
+    #[test]
+    fn generate_password_from_seed_hashes_seed() {
+        let pool = Pool::digits();
+        let digest: [u8; 32] = Sha256::digest(b"").into();
+
+        assert_eq!(
+            generate_password_from_seed(&pool, 12, b""),
+            generate_password_seeded(&pool, 12, digest)
+        );
+    }
+
+    #[test]
+    fn generate_n_passwords_seeded_known_output() {
+        let pool = Pool::digits();
+
+        assert_eq!(
+            generate_n_passwords_seeded(&pool, 8, 3, [42; 32]),
+            vec!["18459447", "80312007", "05485847"]
+        );
+    }
+
+    #[test]
+    fn generate_n_passwords_seeded_assert_count() {
+        let vec_passwords = generate_n_passwords_seeded(&Pool::digits(), 15, 100, [3; 32]);
+
+        assert_eq!(vec_passwords.len(), 100);
+        assert!(vec_passwords.iter().all(|p| p.len() == 15));
+    }
+}
@@ -0,0 +1,157 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Errors returned by the fallible generation functions
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PassgenError {
+    /// The pool contains no elements
+    EmptyPool,
+    /// The requested password length is zero
+    ZeroLength,
+    /// The excluded chars remove every char of the pool
+    AllCharsExcluded,
+    /// The word list contains no elements
+    EmptyWordList,
+    /// The pool contains chars which are not ASCII
+    NonAsciiPool,
+    /// The password is too short to hold every required char
+    LengthTooShort {
+        /// Requested password length
+        length: usize,
+        /// Number of chars that must be placed in the password
+        required: usize,
+    },
+    /// The number of weights differs from the number of chars in the pool
+    WeightsMismatch {
+        /// Number of chars in the pool
+        pool: usize,
+        /// Number of weights
+        weights: usize,
+    },
+    /// All weights are zero
+    InvalidWeights,
+    /// A probability is negative or NaN, or the probabilities don't add up to 1
+    InvalidProbabilities,
+    /// The pattern contains a reserved char which is not a placeholder
+    UnknownPlaceholder(char),
+    /// The pattern ends with a backslash which escapes nothing
+    DanglingEscape,
+    /// The mask names a pool which is not in the named pools
+    UnknownPoolName(String),
+    /// The mask opens a `{` placeholder which is never closed
+    UnclosedPlaceholder,
+    /// The pool has fewer chars than the generation method needs
+    PoolTooSmall {
+        /// Number of chars in the pool
+        len: usize,
+        /// Minimum number of chars
+        required: usize,
+    },
+    /// The requested entropy is NaN, infinite or, where it is rejected, negative
+    InvalidEntropy,
+    /// More chars than allowed would be added to the pool
+    PoolTooLarge {
+        /// Maximum number of chars of the pool
+        max_size: usize,
+    },
+    /// No candidate satisfied the constraints within the maximum number of attempts
+    MaxAttemptsExceeded {
+        /// Number of candidates drawn
+        attempts: usize,
+    },
+    /// The minimum length of a length range is greater than its maximum
+    InvalidLengthRange {
+        /// Minimum length
+        min: usize,
+        /// Maximum length
+        max: usize,
+    },
+    /// The password holding the requested entropy would be too long to allocate
+    EntropyTooLarge,
+}
+
+impl fmt::Display for PassgenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PassgenError::EmptyPool => write!(f, "Pool contains no elements!"),
+            PassgenError::ZeroLength => write!(f, "Password length must be greater than zero!"),
+            PassgenError::AllCharsExcluded => write!(f, "All chars of the pool are excluded!"),
+            PassgenError::EmptyWordList => write!(f, "Word list contains no elements!"),
+            PassgenError::NonAsciiPool => write!(f, "Pool contains non-ASCII chars!"),
+            PassgenError::LengthTooShort { length, required } => write!(
+                f,
+                "Password length {} is shorter than the {} required chars!",
+                length, required
+            ),
+            PassgenError::WeightsMismatch { pool, weights } => write!(
+                f,
+                "Expected {} weights, one per char of the pool, got {}!",
+                pool, weights
+            ),
+            PassgenError::InvalidWeights => write!(f, "At least one weight must be non-zero!"),
+            PassgenError::InvalidProbabilities => {
+                write!(f, "Probabilities must be non-negative and add up to 1!")
+            }
+            PassgenError::UnknownPlaceholder(ch) => {
+                write!(f, "Unknown placeholder '{}' in pattern!", ch)
+            }
+            PassgenError::DanglingEscape => write!(f, "Pattern ends with a dangling escape!"),
+            PassgenError::UnknownPoolName(name) => write!(f, "Unknown pool '{}' in mask!", name),
+            PassgenError::UnclosedPlaceholder => write!(f, "Mask has an unclosed placeholder!"),
+            PassgenError::PoolTooSmall { len, required } => write!(
+                f,
+                "Pool contains {} chars, at least {} are required!",
+                len, required
+            ),
+            PassgenError::InvalidEntropy => {
+                write!(f, "Entropy must be a finite, non-negative number!")
+            }
+            PassgenError::PoolTooLarge { max_size } => {
+                write!(f, "Pool would contain more than {} chars!", max_size)
+            }
+            PassgenError::MaxAttemptsExceeded { attempts } => {
+                write!(f, "No valid password found in {} attempts!", attempts)
+            }
+            PassgenError::InvalidLengthRange { min, max } => write!(
+                f,
+                "Minimum length {} is greater than the maximum length {}!",
+                min, max
+            ),
+            PassgenError::EntropyTooLarge => {
+                write!(f, "Entropy is too large for a password to hold it!")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PassgenError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_empty_pool() {
+        assert_eq!(
+            PassgenError::EmptyPool.to_string(),
+            "Pool contains no elements!"
+        );
+    }
+
+    #[test]
+    fn display_zero_length() {
+        assert_eq!(
+            PassgenError::ZeroLength.to_string(),
+            "Password length must be greater than zero!"
+        );
+    }
+
+    #[test]
+    fn is_std_error() {
+        let err: Box<dyn std::error::Error> = Box::new(PassgenError::EmptyPool);
+
+        assert_eq!(err.to_string(), "Pool contains no elements!");
+    }
+}
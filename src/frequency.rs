@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+/// Count the occurrences of each char across a batch of passwords, for example to check
+/// that every char of the pool is used, or that they are used uniformly with a chi-square
+/// test.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, char_frequencies, generate_n_passwords};
+/// let passwords = generate_n_passwords(&Pool::digits(), 20, 500);
+/// let frequencies = char_frequencies(&passwords);
+///
+/// assert_eq!(frequencies.len(), 10);
+/// assert_eq!(frequencies.values().sum::<usize>(), 10_000);
+/// ```
+pub fn char_frequencies(passwords: &[String]) -> HashMap<char, usize> {
+    let mut frequencies = HashMap::new();
+    for ch in passwords.iter().flat_map(|password| password.chars()) {
+        *frequencies.entry(ch).or_insert(0) += 1;
+    }
+
+    frequencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_n_passwords_with_rng, Pool};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn char_frequencies_counts_chars() {
+        let passwords = [String::from("aab"), String::from("bé"), String::new()];
+        let frequencies = char_frequencies(&passwords);
+
+        assert_eq!(frequencies.len(), 3);
+        assert_eq!(frequencies[&'a'], 2);
+        assert_eq!(frequencies[&'b'], 2);
+        assert_eq!(frequencies[&'é'], 1);
+        assert!(char_frequencies(&[]).is_empty());
+    }
+
+    #[test]
+    fn generated_chars_pass_chi_square() {
+        let pool = Pool::alphanumeric();
+        let mut rng = StdRng::seed_from_u64(42);
+        let passwords = generate_n_passwords_with_rng(&pool, 31, 2000, &mut rng);
+        let frequencies = char_frequencies(&passwords);
+        let expected = (31 * 2000) as f64 / pool.len() as f64;
+        let chi_square: f64 = pool
+            .iter()
+            .map(|ch| {
+                let observed = frequencies.get(ch).copied().unwrap_or(0) as f64;
+                (observed - expected).powi(2) / expected
+            })
+            .sum();
+
+        assert_eq!(frequencies.len(), pool.len());
+        // Critical value for 61 degrees of freedom at p = 0.001
+        assert!(chi_square < 100.9, "chi-square {}", chi_square);
+    }
+}
@@ -0,0 +1,611 @@
+use crate::repeats::{sample_no_repeats, validate_no_repeats};
+use crate::requirements::{pick, place_and_shuffle};
+use crate::{sample_password, validate, PassgenError, Pool};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+/// Length used by [`PasswordGenerator::new`] until [`PasswordGenerator::length`] is called
+pub const DEFAULT_LENGTH: usize = 16;
+
+/// Reusable password generator configured with a fluent builder.
+///
+/// The configuration is checked by [`PasswordGenerator::build`] and again by every
+/// generation call, so an invalid generator never panics.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{Pool, PasswordGenerator};
+/// let generator = PasswordGenerator::new(&Pool::alphanumeric())
+///     .length(20)
+///     .exclude_chars("l1IO0")
+///     .require(&Pool::digits())
+///     .no_consecutive_repeats(true)
+///     .count(5)
+///     .build()
+///     .unwrap();
+///
+/// let passwords = generator.generate_n().unwrap();
+///
+/// assert_eq!(passwords.len(), 5);
+/// assert!(passwords.iter().all(|p| p.chars().count() == 20));
+/// assert!(passwords.iter().all(|p| p.chars().any(|ch| ch.is_ascii_digit())));
+/// # }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PasswordGenerator {
+    pool: Pool,
+    length: usize,
+    excluded: Pool,
+    required: Vec<Pool>,
+    no_consecutive_repeats: bool,
+    ascii_only: bool,
+    count: usize,
+}
+
+impl PasswordGenerator {
+    /// Create generator using `pool`, producing one password of [`DEFAULT_LENGTH`] chars
+    pub fn new(pool: &Pool) -> Self {
+        PasswordGenerator {
+            pool: pool.clone(),
+            length: DEFAULT_LENGTH,
+            excluded: Pool::new(),
+            required: Vec::new(),
+            no_consecutive_repeats: false,
+            ascii_only: false,
+            count: 1,
+        }
+    }
+
+    /// Replace the pool chars are picked from
+    pub fn pool(mut self, pool: &Pool) -> Self {
+        self.pool = pool.clone();
+
+        self
+    }
+
+    /// Set the number of chars of each password
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+
+        self
+    }
+
+    /// Never use the chars of `chars`, even if they are in the pool.
+    /// Calling this several times accumulates the exclusions.
+    pub fn exclude_chars(mut self, chars: &str) -> Self {
+        self.excluded.extend_from_string(chars);
+
+        self
+    }
+
+    /// Require at least one char of `pool` in every password. Calling this several times
+    /// adds requirements.
+    ///
+    /// As with [`generate_password_with_requirements`](crate::generate_password_with_requirements),
+    /// `pool` does not have to be a subset of the generator pool, but the excluded chars are
+    /// removed from it too.
+    pub fn require(mut self, pool: &Pool) -> Self {
+        self.required.push(pool.clone());
+
+        self
+    }
+
+    /// Never put the same char twice in a row when `enabled` is true. See
+    /// [`generate_password_no_repeats`](crate::generate_password_no_repeats) for the effect on
+    /// entropy.
+    pub fn no_consecutive_repeats(mut self, enabled: bool) -> Self {
+        self.no_consecutive_repeats = enabled;
+
+        self
+    }
+
+    /// Fail with [`PassgenError::NonAsciiPool`] instead of generating when `enabled` is true
+    /// and the pool or a required pool contains a non-ASCII char, for backends which only
+    /// accept ASCII passwords. See [`Pool::non_ascii_chars`] to report the offending chars.
+    pub fn ascii_only(mut self, enabled: bool) -> Self {
+        self.ascii_only = enabled;
+
+        self
+    }
+
+    /// Set the number of passwords returned by [`PasswordGenerator::generate_n`]
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+
+        self
+    }
+
+    /// Check the configuration and return the generator.
+    ///
+    /// # Errors
+    /// Returns [`PassgenError::EmptyPool`] if the pool or a required pool is empty,
+    /// [`PassgenError::ZeroLength`] if the length is zero, [`PassgenError::AllCharsExcluded`]
+    /// if the exclusions remove every char of the pool or of a required pool,
+    /// [`PassgenError::NonAsciiPool`] if only ASCII is allowed and a pool contains another
+    /// char once the exclusions are removed, [`PassgenError::LengthTooShort`] if there are
+    /// more requirements than chars and [`PassgenError::PoolTooSmall`] if consecutive repeats
+    /// are forbidden and the pool has a single char.
+    pub fn build(self) -> Result<Self, PassgenError> {
+        self.config()?;
+
+        Ok(self)
+    }
+
+    /// Generate one password.
+    ///
+    /// # Errors
+    /// Same as [`PasswordGenerator::build`].
+    #[cfg(feature = "std")]
+    pub fn generate(&self) -> Result<String, PassgenError> {
+        self.generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate one password using the given random number generator.
+    ///
+    /// # Errors
+    /// Same as [`PasswordGenerator::build`].
+    pub fn generate_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<String, PassgenError> {
+        Ok(self.config()?.sample(rng))
+    }
+
+    /// Generate as many passwords as configured with [`PasswordGenerator::count`].
+    ///
+    /// # Errors
+    /// Same as [`PasswordGenerator::build`].
+    #[cfg(feature = "std")]
+    pub fn generate_n(&self) -> Result<Vec<String>, PassgenError> {
+        let config = self.config()?;
+        let mut rng = rand::thread_rng();
+
+        Ok((0..self.count).map(|_| config.sample(&mut rng)).collect())
+    }
+
+    /// Returns an infinite iterator of passwords, generated lazily with [`rand::thread_rng`].
+    /// [`PasswordGenerator::count`] is ignored, use [`Iterator::take`] instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Pool, PasswordGenerator};
+    /// let generator = PasswordGenerator::new(&Pool::digits()).length(6);
+    /// let passwords: Vec<String> = generator.iter_passwords().unwrap().take(10).collect();
+    ///
+    /// assert_eq!(passwords.len(), 10);
+    /// ```
+    ///
+    /// # Errors
+    /// Same as [`PasswordGenerator::build`].
+    #[cfg(feature = "std")]
+    pub fn iter_passwords(&self) -> Result<Passwords<ThreadRng>, PassgenError> {
+        self.iter_passwords_with_rng(rand::thread_rng())
+    }
+
+    /// Returns an infinite iterator of passwords generated with `rng`.
+    ///
+    /// # Errors
+    /// Same as [`PasswordGenerator::build`].
+    pub fn iter_passwords_with_rng<R: Rng>(&self, rng: R) -> Result<Passwords<R>, PassgenError> {
+        Ok(Passwords {
+            config: self.config()?,
+            rng,
+        })
+    }
+
+    /// Validated configuration, with the excluded chars removed from every pool
+    fn config(&self) -> Result<Config, PassgenError> {
+        validate(&self.pool, self.length)?;
+        if self.required.iter().any(Pool::is_empty) {
+            return Err(PassgenError::EmptyPool);
+        }
+
+        let pool = self.pool.difference(&self.excluded);
+        let required: Vec<Pool> = self
+            .required
+            .iter()
+            .map(|required| required.difference(&self.excluded))
+            .collect();
+        if pool.is_empty() || required.iter().any(Pool::is_empty) {
+            return Err(PassgenError::AllCharsExcluded);
+        }
+        if self.ascii_only && !(pool.is_ascii() && required.iter().all(Pool::is_ascii)) {
+            return Err(PassgenError::NonAsciiPool);
+        }
+        if self.length < required.len() {
+            return Err(PassgenError::LengthTooShort {
+                length: self.length,
+                required: required.len(),
+            });
+        }
+        if self.no_consecutive_repeats {
+            validate_no_repeats(&pool, self.length)?;
+        }
+
+        Ok(Config {
+            pool,
+            required,
+            length: self.length,
+            no_consecutive_repeats: self.no_consecutive_repeats,
+        })
+    }
+}
+
+/// Configuration of a [`PasswordGenerator`] once validated
+#[derive(Debug, Clone)]
+struct Config {
+    pool: Pool,
+    required: Vec<Pool>,
+    length: usize,
+    no_consecutive_repeats: bool,
+}
+
+impl Config {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        match (self.required.is_empty(), self.no_consecutive_repeats) {
+            (true, false) => sample_password(&self.pool, self.length, rng),
+            (true, true) => sample_no_repeats(&self.pool, self.length, rng),
+            (false, false) => {
+                let required: Vec<(&Pool, usize)> =
+                    self.required.iter().map(|pool| (pool, 1)).collect();
+
+                place_and_shuffle(&required, &self.pool, self.length, rng)
+            }
+            (false, true) => self.sample_no_repeats_with_requirements(rng),
+        }
+    }
+
+    /// Draw a password without consecutive repeats, then overwrite one random position for
+    /// each requirement it does not satisfy yet. A requirement is only unsatisfied when none
+    /// of its chars is in the password, so the written char never equals its neighbours.
+    /// One position satisfying each requirement is locked so later ones do not overwrite it,
+    /// which leaves a free position as long as there are fewer requirements than chars.
+    fn sample_no_repeats_with_requirements<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let mut chars: Vec<char> = sample_no_repeats(&self.pool, self.length, rng)
+            .chars()
+            .collect();
+        let mut locked = vec![false; chars.len()];
+
+        for required in &self.required {
+            let is_locked_match = |(ch, locked): (&char, &bool)| *locked && required.contains(*ch);
+            if chars.iter().zip(&locked).any(is_locked_match) {
+                continue;
+            }
+
+            let idx = match chars.iter().position(|ch| required.contains(*ch)) {
+                Some(idx) => idx,
+                None => {
+                    let free: Vec<usize> = (0..chars.len()).filter(|idx| !locked[*idx]).collect();
+                    let idx = free[rng.gen_range(0..free.len())];
+                    chars[idx] = pick(required, rng);
+
+                    idx
+                }
+            };
+            locked[idx] = true;
+        }
+
+        chars.into_iter().collect()
+    }
+}
+
+/// Infinite iterator of passwords returned by [`PasswordGenerator::iter_passwords`]
+#[derive(Debug, Clone)]
+pub struct Passwords<R> {
+    config: Config,
+    rng: R,
+}
+
+impl<R: Rng> Iterator for Passwords<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.config.sample(&mut self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<R: Rng> FusedIterator for Passwords<R> {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn new_defaults() {
+        let generator = PasswordGenerator::new(&Pool::digits());
+        let password = generator.generate().unwrap();
+
+        assert_eq!(password.chars().count(), DEFAULT_LENGTH);
+        assert_eq!(generator.generate_n().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn pool_replaces_pool() {
+        let generator = PasswordGenerator::new(&Pool::digits()).pool(&"ab".parse().unwrap());
+        let password = generator.generate().unwrap();
+
+        assert!(password.chars().all(|ch| ch == 'a' || ch == 'b'));
+    }
+
+    #[test]
+    fn length_sets_length() {
+        let generator = PasswordGenerator::new(&Pool::digits()).length(42);
+
+        assert_eq!(generator.generate().unwrap().chars().count(), 42);
+    }
+
+    #[test]
+    fn exclude_chars_removes_chars() {
+        let generator = PasswordGenerator::new(&"abc".parse().unwrap())
+            .exclude_chars("a")
+            .exclude_chars("b")
+            .length(100);
+        let password = generator.generate().unwrap();
+
+        assert_eq!(password, "c".repeat(100));
+    }
+
+    #[test]
+    fn count_sets_count() {
+        let generator = PasswordGenerator::new(&Pool::digits()).count(7);
+        let passwords = generator.generate_n().unwrap();
+
+        assert_eq!(passwords.len(), 7);
+        assert!(passwords.iter().all(|p| p.len() == DEFAULT_LENGTH));
+    }
+
+    #[test]
+    fn count_zero() {
+        let generator = PasswordGenerator::new(&Pool::digits()).count(0);
+
+        assert!(generator.generate_n().unwrap().is_empty());
+    }
+
+    #[test]
+    fn build_valid() {
+        let generator = PasswordGenerator::new(&Pool::digits()).length(8);
+
+        assert_eq!(generator.clone().build(), Ok(generator));
+    }
+
+    #[test]
+    fn build_empty_pool() {
+        let result = PasswordGenerator::new(&Pool::new()).build();
+
+        assert_eq!(result, Err(PassgenError::EmptyPool));
+    }
+
+    #[test]
+    fn build_zero_length() {
+        let result = PasswordGenerator::new(&Pool::digits()).length(0).build();
+
+        assert_eq!(result, Err(PassgenError::ZeroLength));
+    }
+
+    #[test]
+    fn build_all_chars_excluded() {
+        let result = PasswordGenerator::new(&"abc".parse().unwrap())
+            .exclude_chars("cba")
+            .build();
+
+        assert_eq!(result, Err(PassgenError::AllCharsExcluded));
+    }
+
+    #[test]
+    fn generate_invalid_config() {
+        let generator = PasswordGenerator::new(&Pool::digits()).exclude_chars("0123456789");
+
+        assert_eq!(generator.generate(), Err(PassgenError::AllCharsExcluded));
+        assert_eq!(generator.generate_n(), Err(PassgenError::AllCharsExcluded));
+    }
+
+    #[test]
+    fn generate_with_rng_same_seed() {
+        let generator = PasswordGenerator::new(&Pool::alphanumeric());
+
+        assert_eq!(
+            generator.generate_with_rng(&mut StdRng::seed_from_u64(42)),
+            generator.generate_with_rng(&mut StdRng::seed_from_u64(42))
+        );
+    }
+
+    #[test]
+    fn iter_passwords_take() {
+        let generator = PasswordGenerator::new(&Pool::digits()).length(12);
+        let passwords: Vec<String> = generator.iter_passwords().unwrap().take(50).collect();
+
+        assert_eq!(passwords.len(), 50);
+        assert!(passwords.iter().all(|p| p.chars().count() == 12));
+    }
+
+    #[test]
+    fn iter_passwords_never_ends() {
+        let generator = PasswordGenerator::new(&"a".parse().unwrap()).length(1);
+        let mut iter = generator.iter_passwords().unwrap();
+
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+        assert_eq!(iter.by_ref().take(10_000).count(), 10_000);
+        assert_eq!(iter.next(), Some("a".to_owned()));
+    }
+
+    #[test]
+    fn iter_passwords_uses_exclusions() {
+        let generator = PasswordGenerator::new(&"ab".parse().unwrap()).exclude_chars("a");
+
+        assert!(generator
+            .iter_passwords()
+            .unwrap()
+            .take(20)
+            .all(|p| !p.contains('a')));
+    }
+
+    #[test]
+    fn iter_passwords_invalid_config() {
+        let result = PasswordGenerator::new(&Pool::new()).iter_passwords();
+
+        assert_eq!(result.err(), Some(PassgenError::EmptyPool));
+    }
+
+    #[test]
+    fn iter_passwords_with_rng_matches_generate_with_rng() {
+        let generator = PasswordGenerator::new(&Pool::alphanumeric());
+        let mut rng = StdRng::seed_from_u64(42);
+        let expected = vec![
+            generator.generate_with_rng(&mut rng).unwrap(),
+            generator.generate_with_rng(&mut rng).unwrap(),
+        ];
+        let passwords: Vec<String> = generator
+            .iter_passwords_with_rng(StdRng::seed_from_u64(42))
+            .unwrap()
+            .take(2)
+            .collect();
+
+        assert_eq!(passwords, expected);
+    }
+
+    #[test]
+    fn require_always_satisfied() {
+        let generator = PasswordGenerator::new(&Pool::lowercase())
+            .length(3)
+            .require(&Pool::digits())
+            .require(&Pool::uppercase());
+
+        for password in generator.iter_passwords().unwrap().take(1_000) {
+            assert!(password.chars().any(|ch| ch.is_ascii_digit()));
+            assert!(password.chars().any(|ch| ch.is_ascii_uppercase()));
+        }
+    }
+
+    #[test]
+    fn require_removes_excluded_chars() {
+        let generator = PasswordGenerator::new(&Pool::lowercase())
+            .require(&"0O".parse().unwrap())
+            .exclude_chars("O");
+
+        assert!(generator
+            .iter_passwords()
+            .unwrap()
+            .take(100)
+            .all(|p| p.contains('0') && !p.contains('O')));
+    }
+
+    #[test]
+    fn no_consecutive_repeats_never_repeats() {
+        let generator = PasswordGenerator::new(&"ab".parse().unwrap())
+            .length(10)
+            .no_consecutive_repeats(true);
+
+        for password in generator.iter_passwords().unwrap().take(100) {
+            assert!(password == "ababababab" || password == "bababababa");
+        }
+    }
+
+    #[test]
+    fn no_consecutive_repeats_with_requirements() {
+        let generator = PasswordGenerator::new(&"ab".parse().unwrap())
+            .length(6)
+            .require(&"X".parse().unwrap())
+            .require(&"Y".parse().unwrap())
+            .require(&"XY".parse().unwrap())
+            .no_consecutive_repeats(true);
+
+        for password in generator.iter_passwords().unwrap().take(1_000) {
+            let chars: Vec<char> = password.chars().collect();
+
+            assert_eq!(chars.len(), 6);
+            assert!(password.contains('X') && password.contains('Y'));
+            assert!(chars.windows(2).all(|w| w[0] != w[1]));
+        }
+    }
+
+    #[test]
+    fn build_invalid_requirements() {
+        let generator = PasswordGenerator::new(&Pool::digits()).length(2);
+
+        assert_eq!(
+            generator.clone().require(&Pool::new()).build(),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generator
+                .clone()
+                .require(&"ab".parse().unwrap())
+                .exclude_chars("ab")
+                .build(),
+            Err(PassgenError::AllCharsExcluded)
+        );
+        assert_eq!(
+            generator
+                .require(&Pool::lowercase())
+                .require(&Pool::uppercase())
+                .require(&Pool::digits())
+                .build(),
+            Err(PassgenError::LengthTooShort {
+                length: 2,
+                required: 3
+            })
+        );
+    }
+
+    #[test]
+    fn build_no_consecutive_repeats_single_char() {
+        let result = PasswordGenerator::new(&"ab".parse().unwrap())
+            .exclude_chars("a")
+            .no_consecutive_repeats(true)
+            .build();
+
+        assert_eq!(
+            result,
+            Err(PassgenError::PoolTooSmall {
+                len: 1,
+                required: 2
+            })
+        );
+    }
+
+    #[test]
+    fn ascii_only_rejects_non_ascii_pools() {
+        let pool: Pool = "abcé".parse().unwrap();
+
+        assert_eq!(
+            PasswordGenerator::new(&pool).ascii_only(true).build(),
+            Err(PassgenError::NonAsciiPool)
+        );
+        assert_eq!(
+            PasswordGenerator::new(&Pool::digits())
+                .require(&"€".parse().unwrap())
+                .ascii_only(true)
+                .build(),
+            Err(PassgenError::NonAsciiPool)
+        );
+        assert!(PasswordGenerator::new(&pool).build().is_ok());
+    }
+
+    #[test]
+    fn ascii_only_applies_after_exclusions() {
+        let generator = PasswordGenerator::new(&"abcé".parse().unwrap())
+            .exclude_chars("é")
+            .ascii_only(true);
+
+        assert!(generator.generate().unwrap().is_ascii());
+    }
+
+    #[test]
+    fn clone_is_reusable() {
+        let generator = PasswordGenerator::new(&Pool::digits()).length(4);
+        let longer = generator.clone().length(8);
+
+        assert_eq!(generator.generate().unwrap().len(), 4);
+        assert_eq!(longer.generate().unwrap().len(), 8);
+    }
+}
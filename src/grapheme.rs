@@ -0,0 +1,289 @@
+use crate::{unwrap_or_empty, PassgenError};
+use alloc::string::String;
+use core::convert::Infallible;
+use core::fmt;
+use core::str::FromStr;
+use indexmap::IndexSet;
+use rand::Rng;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "std")]
+type GraphemeSet = IndexSet<String>;
+
+#[cfg(not(feature = "std"))]
+type GraphemeSet = IndexSet<String, core::hash::BuildHasherDefault<crate::CharHasher>>;
+
+/// Collection of unique extended grapheme clusters, the units a user perceives as a single
+/// character.
+///
+/// [`Pool`](crate::Pool) stores `char`s, so an emoji with a skin tone modifier, a flag or a
+/// letter followed by a combining accent is split into several scalar values, which are then
+/// picked independently and produce garbled passwords. A `GraphemePool` keeps each cluster
+/// whole.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::GraphemePool;
+/// let pool = GraphemePool::from_graphemes("e\u{301}🇫🇷👍🏽");
+///
+/// assert_eq!(pool.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct GraphemePool(GraphemeSet);
+
+impl GraphemePool {
+    /// Create new empty pool
+    pub fn new() -> Self {
+        GraphemePool(GraphemeSet::default())
+    }
+
+    /// Create pool with the grapheme clusters of `s`, ignoring duplicates
+    pub fn from_graphemes(s: &str) -> Self {
+        GraphemePool(s.graphemes(true).map(String::from).collect())
+    }
+
+    /// Return number of grapheme clusters in the pool
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if pool contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Check if grapheme cluster exists in the pool
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::GraphemePool;
+    /// let pool = GraphemePool::from_graphemes("🇫🇷🇩🇪");
+    ///
+    /// assert!(pool.contains("🇩🇪"));
+    /// assert!(!pool.contains("🇫"));
+    /// ```
+    pub fn contains(&self, grapheme: &str) -> bool {
+        self.0.contains(grapheme)
+    }
+
+    /// Extracts all grapheme clusters from string and adds them to the pool
+    pub fn extend_from_string(&mut self, s: &str) -> &mut Self {
+        self.0.extend(s.graphemes(true).map(String::from));
+
+        self
+    }
+
+    /// Calculates entropy of a password of `length` grapheme clusters picked from the pool,
+    /// using the number of clusters as pool size.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::GraphemePool;
+    /// let pool = GraphemePool::from_graphemes("🇫🇷🇩🇪🇮🇹🇪🇸");
+    ///
+    /// assert_eq!(pool.entropy(5), 10_f64);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn entropy(&self, length: usize) -> f64 {
+        crate::calculate_entropy(length, self.len())
+    }
+
+    /// Returns iterator over the grapheme clusters, in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl FromStr for GraphemePool {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(GraphemePool::from_graphemes(s))
+    }
+}
+
+impl fmt::Display for GraphemePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|grapheme| f.write_str(grapheme))
+    }
+}
+
+/// Generate random password of `length` grapheme clusters picked from `pool`.
+///
+/// `length` counts the picked clusters, which only match the graphemes of the password when
+/// no cluster joins its neighbour. Some do: a lone regional indicator like `"🇫"` pairs with
+/// the next one into a flag, a cluster starting with a combining mark attaches to the
+/// previous one and Hangul jamo combine into syllables. Keep such clusters out of `pool`
+/// when the number of graphemes matters.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{GraphemePool, generate_password_graphemes};
+/// use unicode_segmentation::UnicodeSegmentation;
+///
+/// let pool = GraphemePool::from_graphemes("🇫🇷🇩🇪🇮🇹");
+/// let password = generate_password_graphemes(&pool, 6);
+///
+/// assert_eq!(password.graphemes(true).count(), 6);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty. See [`try_generate_password_graphemes`] for a non-panicking
+/// version, which rejects a zero `length`.
+#[cfg(feature = "std")]
+pub fn generate_password_graphemes(pool: &GraphemePool, length: usize) -> String {
+    unwrap_or_empty(try_generate_password_graphemes(pool, length))
+}
+
+/// Generate random password of grapheme clusters, returning an error instead of panicking.
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty and [`PassgenError::ZeroLength`]
+/// if `length` is zero.
+#[cfg(feature = "std")]
+pub fn try_generate_password_graphemes(
+    pool: &GraphemePool,
+    length: usize,
+) -> Result<String, PassgenError> {
+    validate_graphemes(pool, length)?;
+
+    Ok(sample_graphemes(pool, length, &mut rand::thread_rng()))
+}
+
+/// Generate random password of grapheme clusters using the given random number generator.
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_graphemes_with_rng<R: Rng + ?Sized>(
+    pool: &GraphemePool,
+    length: usize,
+    rng: &mut R,
+) -> String {
+    unwrap_or_empty(validate_graphemes(pool, length));
+
+    sample_graphemes(pool, length, rng)
+}
+
+fn validate_graphemes(pool: &GraphemePool, length: usize) -> Result<(), PassgenError> {
+    if pool.is_empty() {
+        return Err(PassgenError::EmptyPool);
+    }
+    if length == 0 {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    Ok(())
+}
+
+fn sample_graphemes<R: Rng + ?Sized>(pool: &GraphemePool, length: usize, rng: &mut R) -> String {
+    let max_len = pool.iter().map(str::len).max().unwrap_or(0);
+    let mut password = String::with_capacity(max_len * length);
+    for _ in 0..length {
+        password.push_str(&pool.0[rng.gen_range(0..pool.len())]);
+    }
+
+    password
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn from_graphemes_keeps_clusters() {
+        let pool = GraphemePool::from_graphemes("ae\u{301}👍🏽🇫🇷a");
+
+        assert_eq!(pool.len(), 4);
+        assert_eq!(
+            pool.iter().collect::<Vec<&str>>(),
+            ["a", "e\u{301}", "👍🏽", "🇫🇷"]
+        );
+        assert_eq!(pool.to_string(), "ae\u{301}👍🏽🇫🇷");
+    }
+
+    #[test]
+    fn generate_password_graphemes_assert_len() {
+        let pool = GraphemePool::from_graphemes("👍🏽👍🏿");
+        let password = generate_password_graphemes(&pool, 10);
+
+        assert_eq!(password.graphemes(true).count(), 10);
+        assert!(password
+            .graphemes(true)
+            .all(|grapheme| grapheme == "👍🏽" || grapheme == "👍🏿"));
+    }
+
+    #[test]
+    fn generate_password_graphemes_with_rng_same_seed() {
+        let pool = GraphemePool::from_graphemes("🇫🇷🇩🇪🇮🇹");
+
+        assert_eq!(
+            generate_password_graphemes_with_rng(&pool, 8, &mut StdRng::seed_from_u64(42)),
+            generate_password_graphemes_with_rng(&pool, 8, &mut StdRng::seed_from_u64(42))
+        );
+    }
+
+    #[test]
+    fn generate_password_graphemes_zero_length() {
+        let pool = GraphemePool::from_graphemes("👍🏽👍🏿");
+
+        assert_eq!(generate_password_graphemes(&pool, 0), "");
+        assert_eq!(
+            generate_password_graphemes_with_rng(&pool, 0, &mut StdRng::seed_from_u64(42)),
+            ""
+        );
+    }
+
+    #[test]
+    fn try_generate_password_graphemes_errors() {
+        assert_eq!(
+            try_generate_password_graphemes(&GraphemePool::new(), 4),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            try_generate_password_graphemes(&GraphemePool::from_graphemes("ab"), 0),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+
+    #[test]
+    fn flag_and_zwj_sequence_are_single_graphemes() {
+        // Regional indicators F and R, then man, woman and girl joined by ZWJ
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let pool: GraphemePool = format!("🇫🇷{}", family).parse().unwrap();
+
+        assert_eq!(pool.len(), 2);
+        assert!(pool.contains("🇫🇷"));
+        assert!(pool.contains(family));
+        assert!(!pool.contains("\u{1f468}"));
+        assert_eq!(pool.entropy(8), 8_f64);
+
+        let password = generate_password_graphemes(&pool, 12);
+        assert_eq!(password.graphemes(true).count(), 12);
+        assert!(password
+            .graphemes(true)
+            .all(|grapheme| pool.contains(grapheme)));
+    }
+
+    #[test]
+    fn lone_regional_indicators_join_into_flags() {
+        let pool = GraphemePool::from_graphemes("🇫");
+        let password = generate_password_graphemes(&pool, 4);
+
+        assert_eq!(password, "🇫🇫🇫🇫");
+        assert_eq!(password.graphemes(true).count(), 2);
+    }
+
+    #[test]
+    fn extend_from_string_ignores_duplicates() {
+        let mut pool = GraphemePool::from_graphemes("🇫🇷a");
+        pool.extend_from_string("🇫🇷🇩🇪a");
+
+        assert_eq!(pool.iter().collect::<Vec<&str>>(), ["🇫🇷", "a", "🇩🇪"]);
+    }
+}
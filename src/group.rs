@@ -0,0 +1,54 @@
+use alloc::string::String;
+
+/// Insert `separator` between groups of `group_size` chars of `password`, like
+/// `abcd-efgh-ijkl`, to make a long password easier to read or type.
+///
+/// The last group may be shorter and no separator is added after it. If `group_size` is
+/// zero, `password` is returned unchanged. Chars are counted as `char`s, not bytes.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::group_password;
+/// assert_eq!(group_password("abcdefghij", 4, '-'), "abcd-efgh-ij");
+/// assert_eq!(group_password("abcdefgh", 4, ' '), "abcd efgh");
+/// ```
+pub fn group_password(password: &str, group_size: usize, separator: char) -> String {
+    if group_size == 0 {
+        return String::from(password);
+    }
+
+    let groups = password.chars().count().saturating_sub(1) / group_size;
+    let mut grouped = String::with_capacity(password.len() + groups * separator.len_utf8());
+    for (idx, ch) in password.chars().enumerate() {
+        if idx > 0 && idx % group_size == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(ch);
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_password_no_trailing_separator() {
+        assert_eq!(group_password("abcdefghijkl", 4, '-'), "abcd-efgh-ijkl");
+        assert_eq!(group_password("abcde", 2, '-'), "ab-cd-e");
+        assert_eq!(group_password("abc", 3, '-'), "abc");
+        assert_eq!(group_password("ab", 5, '-'), "ab");
+        assert_eq!(group_password("", 4, '-'), "");
+    }
+
+    #[test]
+    fn group_password_zero_group_size() {
+        assert_eq!(group_password("abcdef", 0, '-'), "abcdef");
+    }
+
+    #[test]
+    fn group_password_counts_chars() {
+        assert_eq!(group_password("äöüéè", 2, '·'), "äö·üé·è");
+    }
+}
@@ -0,0 +1,267 @@
+//! Detection of keyboard walks, sequences of neighbouring keys such as `qwer`, `1qaz` or
+//! `zse4`, which are random-looking but among the first guesses of password crackers.
+//!
+//! Keys are adjacent when they are next to each other on a row, or touch each other on the
+//! row above or below, so walks can be horizontal, vertical or diagonal. A shifted char, like
+//! `!` or `Q`, is on the same key as its unshifted one.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Row of keys of a [`KeyboardLayout`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct KeyRow {
+    /// Chars of the keys from left to right, without shift
+    pub keys: &'static str,
+    /// Chars of the same keys with shift, or `""` if there are none
+    pub shifted: &'static str,
+    /// Horizontal position of the first key, in quarters of a key width
+    pub offset: u8,
+}
+
+/// Keyboard layout used to find keyboard walks, as 4 rows of keys from the digits row down
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct KeyboardLayout {
+    rows: [KeyRow; 4],
+}
+
+impl KeyboardLayout {
+    /// US QWERTY layout
+    pub const QWERTY: KeyboardLayout = KeyboardLayout::new([
+        KeyRow {
+            keys: "1234567890-=",
+            shifted: "!@#$%^&*()_+",
+            offset: 0,
+        },
+        KeyRow {
+            keys: "qwertyuiop[]\\",
+            shifted: "QWERTYUIOP{}|",
+            offset: 2,
+        },
+        KeyRow {
+            keys: "asdfghjkl;'",
+            shifted: "ASDFGHJKL:\"",
+            offset: 3,
+        },
+        KeyRow {
+            keys: "zxcvbnm,./",
+            shifted: "ZXCVBNM<>?",
+            offset: 5,
+        },
+    ]);
+
+    /// French AZERTY layout
+    pub const AZERTY: KeyboardLayout = KeyboardLayout::new([
+        KeyRow {
+            keys: "&é\"'(-è_çà)=",
+            shifted: "1234567890°+",
+            offset: 0,
+        },
+        KeyRow {
+            keys: "azertyuiop^$",
+            shifted: "AZERTYUIOP¨£",
+            offset: 2,
+        },
+        KeyRow {
+            keys: "qsdfghjklmù*",
+            shifted: "QSDFGHJKLM%µ",
+            offset: 3,
+        },
+        KeyRow {
+            keys: "<wxcvbn,;:!",
+            shifted: ">WXCVBN?./§",
+            offset: 1,
+        },
+    ]);
+
+    /// German QWERTZ layout
+    pub const QWERTZ: KeyboardLayout = KeyboardLayout::new([
+        KeyRow {
+            keys: "1234567890ß´",
+            shifted: "!\"§$%&/()=?`",
+            offset: 0,
+        },
+        KeyRow {
+            keys: "qwertzuiopü+",
+            shifted: "QWERTZUIOPÜ*",
+            offset: 2,
+        },
+        KeyRow {
+            keys: "asdfghjklöä#",
+            shifted: "ASDFGHJKLÖÄ'",
+            offset: 3,
+        },
+        KeyRow {
+            keys: "<yxcvbnm,.-",
+            shifted: ">YXCVBNM;:_",
+            offset: 1,
+        },
+    ]);
+
+    /// Create layout from its rows, from the digits row down. The offsets place the rows
+    /// relative to each other, 4 being the width of a key.
+    pub const fn new(rows: [KeyRow; 4]) -> Self {
+        KeyboardLayout { rows }
+    }
+
+    /// Returns the rows, from the digits row down
+    pub(crate) fn rows(&self) -> &[KeyRow; 4] {
+        &self.rows
+    }
+
+    /// Returns the row and horizontal position, in quarters of a key width, of the key of `ch`
+    fn position(&self, ch: char) -> Option<(usize, usize)> {
+        self.rows.iter().enumerate().find_map(|(row, keys)| {
+            let column = keys
+                .keys
+                .chars()
+                .position(|key| key == ch)
+                .or_else(|| keys.shifted.chars().position(|key| key == ch))?;
+
+            Some((row, usize::from(keys.offset) + 4 * column))
+        })
+    }
+
+    /// Returns true if the keys of `a` and `b` are different and touch each other
+    fn adjacent(&self, a: char, b: char) -> bool {
+        match (self.position(a), self.position(b)) {
+            (Some((row_a, x_a)), Some((row_b, x_b))) => match row_a.abs_diff(row_b) {
+                0 => x_a.abs_diff(x_b) == 4,
+                1 => x_a.abs_diff(x_b) < 4,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        KeyboardLayout::QWERTY
+    }
+}
+
+/// Find the keyboard walks of at least `min_len` keys on the QWERTY layout. See
+/// [`detect_keyboard_walk_with_layout`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::keyboard::detect_keyboard_walk;
+/// let password = "x1qaz-zse4";
+///
+/// assert_eq!(detect_keyboard_walk(password, 4), [1..5, 6..10]);
+/// assert_eq!(&password[6..10], "zse4");
+/// assert!(detect_keyboard_walk("q1ap", 4).is_empty());
+/// ```
+pub fn detect_keyboard_walk(password: &str, min_len: usize) -> Vec<Range<usize>> {
+    detect_keyboard_walk_with_layout(password, min_len, &KeyboardLayout::QWERTY)
+}
+
+/// Find the keyboard walks of at least `min_len` keys on `layout`, each a longest run of
+/// chars whose consecutive keys are adjacent. Returns their byte ranges, in order, so they
+/// can slice `password`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::keyboard::{KeyboardLayout, detect_keyboard_walk_with_layout};
+/// assert_eq!(
+///     detect_keyboard_walk_with_layout("azer", 4, &KeyboardLayout::AZERTY),
+///     [0..4]
+/// );
+/// assert!(detect_keyboard_walk_with_layout("azer", 4, &KeyboardLayout::QWERTY).is_empty());
+/// ```
+pub fn detect_keyboard_walk_with_layout(
+    password: &str,
+    min_len: usize,
+    layout: &KeyboardLayout,
+) -> Vec<Range<usize>> {
+    let mut walks = Vec::new();
+    let mut start = 0;
+    let mut keys = 0;
+    let mut previous: Option<char> = None;
+
+    for (offset, ch) in password.char_indices() {
+        match previous {
+            Some(key) if layout.adjacent(key, ch) => keys += 1,
+            _ => {
+                if keys >= min_len.max(1) {
+                    walks.push(start..offset);
+                }
+                start = offset;
+                keys = usize::from(layout.position(ch).is_some());
+            }
+        }
+        previous = Some(ch);
+    }
+    if keys >= min_len.max(1) {
+        walks.push(start..password.len());
+    }
+
+    walks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the only walk of at least 4 keys on `layout`
+    fn walk_on(password: &str, layout: &KeyboardLayout) -> Option<Range<usize>> {
+        let walks = detect_keyboard_walk_with_layout(password, 4, layout);
+        assert!(walks.len() <= 1);
+
+        walks.into_iter().next()
+    }
+
+    fn walk(password: &str) -> Option<Range<usize>> {
+        walk_on(password, &KeyboardLayout::QWERTY)
+    }
+
+    #[test]
+    fn horizontal_walks() {
+        assert_eq!(walk("qwer"), Some(0..4));
+        assert_eq!(walk("8poiuy"), Some(1..6));
+        assert_eq!(walk("QwEr"), Some(0..4));
+        assert_eq!(walk("asdf;"), Some(0..4));
+        assert_eq!(walk("qwe"), None);
+    }
+
+    #[test]
+    fn vertical_and_diagonal_walks() {
+        assert_eq!(walk("1qaz"), Some(0..4));
+        assert_eq!(walk("zaq1"), Some(0..4));
+        assert_eq!(walk("2wsx"), Some(0..4));
+        assert_eq!(walk("zse4"), Some(0..4));
+        assert_eq!(walk("3edc"), Some(0..4));
+        assert_eq!(walk("xdr5"), Some(0..4));
+        assert_eq!(walk("1azq"), None);
+    }
+
+    #[test]
+    fn shifted_walks() {
+        assert_eq!(walk("!QAZ"), Some(0..4));
+        assert_eq!(walk("@WsX"), Some(0..4));
+        assert_eq!(walk("!@#$"), Some(0..4));
+        assert_eq!(walk("{P:?"), Some(0..4));
+    }
+
+    #[test]
+    fn several_walks_and_byte_ranges() {
+        assert_eq!(detect_keyboard_walk("qwer-1qaz", 4), [0..4, 5..9]);
+        assert_eq!(walk("é-zxcv-é"), Some(3..7));
+        assert_eq!(detect_keyboard_walk("qq", 1), [0..1, 1..2]);
+        assert!(detect_keyboard_walk("é", 1).is_empty());
+        assert!(detect_keyboard_walk("", 4).is_empty());
+    }
+
+    #[test]
+    fn other_layouts() {
+        let azerty = KeyboardLayout::AZERTY;
+        let qwertz = KeyboardLayout::QWERTZ;
+
+        assert_eq!(walk_on("&aqw", &azerty), Some(0..4));
+        assert_eq!(walk_on("1AQW", &azerty), Some(0..4));
+        assert_eq!(walk_on("rtzu", &qwertz), Some(0..4));
+        assert_eq!(walk_on("<ASW", &qwertz), Some(0..4));
+        assert_eq!(walk("rtzu"), None);
+    }
+}
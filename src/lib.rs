@@ -1,22 +1,212 @@
 //! # libpassgen
 //!
 //! `libpassgen` crate for generating randoms passwords
-
-use indexmap::set::Iter;
+//!
+//! # Features
+//! - `std` (default): functions using [`rand::thread_rng`] or the operating system RNG, and
+//!   entropy calculations. Without it the crate is `no_std` and only needs `alloc`: [`Pool`]
+//!   and the `*_with_rng` functions taking a user-supplied RNG remain available.
+//! - `deterministic`: seeded generation in the `deterministic` module, including from a
+//!   passphrase hashed with SHA-256.
+//! - `parallel` (alias `rayon`): `generate_n_passwords_parallel` and `generate_n_passwords_par`
+//!   using a rayon thread pool.
+//! - `secrecy`: generation of `secrecy::SecretString`s.
+//! - `serde`: `Serialize` and `Deserialize` for [`Pool`].
+//! - `unicode-normalization`: `Pool::from_str_normalized` and `Pool::normalize`, merging
+//!   chars which only differ by their Unicode normalization.
+//! - `unicode-segmentation`: `GraphemePool`, a pool of grapheme clusters such as emoji.
+//! - `zeroize`: `SecurePassword`, wiped from memory when dropped.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::char::ParseCharError;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, BitXor, Bound, Deref, DerefMut, Index, RangeBounds, Sub};
+use core::str::FromStr;
+use indexmap::set::{IntoIter, Iter};
 use indexmap::IndexSet;
-use rand::Rng;
-use std::char::ParseCharError;
-use std::fmt;
-use std::iter::FromIterator;
-use std::ops::{Deref, DerefMut};
-use std::str::FromStr;
-
-/// Collection of unique chars. This is wrapper for [`IndexSet<char>`]
+use rand::distributions::Uniform;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+use rand::{CryptoRng, Rng, RngCore};
+
+mod blocklist;
+mod check;
+mod classes;
+#[cfg(feature = "std")]
+mod ct;
+#[cfg(feature = "deterministic")]
+pub mod deterministic;
+mod error;
+#[cfg(feature = "std")]
+mod frequency;
+mod generator;
+#[cfg(feature = "unicode-segmentation")]
+mod grapheme;
+mod group;
+pub mod keyboard;
+mod matcher;
+#[cfg(feature = "unicode-normalization")]
+mod normalization;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod passphrase;
+#[cfg(feature = "std")]
+mod password;
+#[cfg(feature = "std")]
+mod pattern;
+mod policy;
+#[cfg(feature = "std")]
+mod pronounceable;
+mod repeats;
+mod requirements;
+#[cfg(all(feature = "secrecy", any(feature = "std", feature = "zeroize")))]
+mod secret;
+#[cfg(feature = "zeroize")]
+mod secure;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod spec;
+#[cfg(feature = "std")]
+mod strength;
+#[cfg(feature = "std")]
+mod weighted;
+
+#[cfg(feature = "std")]
+pub use blocklist::{generate_avoiding, generate_until};
+pub use blocklist::{
+    generate_avoiding_with_attempts, generate_avoiding_with_rng, generate_until_with_rng,
+    DEFAULT_MAX_ATTEMPTS,
+};
+pub use check::{append_check_char, verify_check_char};
+pub use classes::ClassCounts;
+#[cfg(feature = "std")]
+pub use ct::generate_password_ct;
+pub use error::PassgenError;
+#[cfg(feature = "std")]
+pub use frequency::char_frequencies;
+pub use generator::{PasswordGenerator, Passwords, DEFAULT_LENGTH};
+#[cfg(all(feature = "unicode-segmentation", feature = "std"))]
+pub use grapheme::{generate_password_graphemes, try_generate_password_graphemes};
+#[cfg(feature = "unicode-segmentation")]
+pub use grapheme::{generate_password_graphemes_with_rng, GraphemePool};
+pub use group::group_password;
+#[cfg(feature = "unicode-normalization")]
+pub use normalization::Normalization;
+#[cfg(feature = "parallel")]
+pub use parallel::{generate_n_passwords_par, generate_n_passwords_parallel};
+#[cfg(feature = "std")]
+pub use passphrase::{
+    generate_passphrase, generate_word_number, try_generate_passphrase, try_generate_word_number,
+};
+pub use passphrase::{generate_passphrase_with_rng, generate_word_number_with_rng};
+#[cfg(feature = "std")]
+pub use password::{generate_n_passwords_typed, generate_password_typed, Password};
+#[cfg(feature = "std")]
+pub use pattern::{generate_from_mask, generate_from_pattern, try_generate_from_pattern};
+pub use policy::{
+    generate_with_policy, ClassKind, Policy, PolicyError, PolicyParseError, Violation,
+};
+#[cfg(feature = "std")]
+pub use pronounceable::{
+    generate_pronounceable, generate_pronounceable_with_pools, pronounceable_entropy, CONSONANTS,
+    VOWELS,
+};
+pub use repeats::generate_password_no_repeats_with_rng;
+#[cfg(feature = "std")]
+pub use repeats::{
+    generate_password_no_repeats, generate_unique_password, try_generate_password_no_repeats,
+};
+#[cfg(feature = "std")]
+pub use requirements::{
+    generate_password_with_all, generate_password_with_counts, generate_password_with_requirements,
+    try_generate_password_with_requirements,
+};
+#[cfg(all(feature = "secrecy", feature = "std"))]
+pub use secret::{generate_n_secrets, generate_secret};
+#[cfg(feature = "zeroize")]
+pub use secure::SecurePassword;
+#[cfg(all(feature = "zeroize", feature = "std"))]
+pub use secure::{generate_n_secure_passwords, generate_secure_password};
+pub use sequence::contains_sequence;
+pub use spec::SpecError;
+#[cfg(feature = "std")]
+pub use strength::{
+    classify_strength, classify_strength_auto, entropy_report, estimate_crack_time,
+    format_crack_time, EntropyReport, Strength,
+};
+#[cfg(feature = "std")]
+pub use weighted::{generate_mixed, generate_weighted, try_generate_mixed};
+
+/// Set of chars backing a [`Pool`]
+#[cfg(feature = "std")]
+type CharSet = IndexSet<char>;
+
+/// Set of chars backing a [`Pool`]. Without `std` there is no randomly seeded hasher, which
+/// is fine for a set of chars that is never built from untrusted keys at scale.
+#[cfg(not(feature = "std"))]
+type CharSet = IndexSet<char, core::hash::BuildHasherDefault<CharHasher>>;
+
+/// FNV-1a hasher of the set backing a [`Pool`] without `std`
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone)]
+pub struct CharHasher(u64);
+
+#[cfg(not(feature = "std"))]
+impl Default for CharHasher {
+    fn default() -> Self {
+        CharHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::hash::Hasher for CharHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Collection of unique chars. This is a wrapper for [`IndexSet<char>`], which it dereferences
+/// to. Without `std` the set uses an FNV-1a hasher instead of the randomly seeded default
+/// hasher, which doesn't exist there.
+///
+/// A char is a single Unicode scalar value, so what a user sees as one character may take
+/// several chars: an emoji with a skin tone, a flag or a letter with a combining accent. Such
+/// clusters are split when building a pool and their parts picked independently. Use
+/// `GraphemePool` from the `unicode-segmentation` feature to keep them whole.
+///
+/// Pools compare as sets: `==` is true when they have the same chars, whatever their order,
+/// so [`Pool::sort`] and [`Pool::shuffle`] keep a pool equal to itself. Use
+/// [`Pool::eq_ordered`] to compare the order too.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::Pool;
+/// let pool: Pool = "abc".parse().unwrap();
+/// let reversed: Pool = "cba".parse().unwrap();
+///
+/// assert_eq!(pool, reversed);
+/// assert!(!pool.eq_ordered(&reversed));
+/// ```
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Pool(IndexSet<char>);
+pub struct Pool(CharSet);
 
 impl Deref for Pool {
-    type Target = IndexSet<char>;
+    type Target = CharSet;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -32,12 +222,55 @@ impl DerefMut for Pool {
 impl FromIterator<char> for Pool {
     fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
         let mut pool = Pool::new();
-        pool.0 = IndexSet::from_iter(iter);
+        pool.0 = CharSet::from_iter(iter);
 
         pool
     }
 }
 
+/// Hashes the chars independently of their order, so that pools which are equal (have the
+/// same chars, whatever their order) have the same hash, as required by [`Hash`]. A pool can
+/// then key a `HashMap` or `HashSet`.
+impl Hash for Pool {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut chars: Vec<char> = self.0.iter().copied().collect();
+        chars.sort_unstable();
+        chars.hash(state);
+    }
+}
+
+/// Returns the char at `index`, in insertion order.
+///
+/// # Panics
+/// Panics if `index` is out of bounds. See [`Pool::char_at`] for a non-panicking version.
+impl Index<usize> for Pool {
+    type Output = char;
+
+    fn index(&self, index: usize) -> &char {
+        &self.0[index]
+    }
+}
+
+/// Iterates over the chars in insertion order, the order used to pick them by index
+impl IntoIterator for Pool {
+    type Item = char;
+    type IntoIter = IntoIter<char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Iterates over the chars in insertion order, the order used to pick them by index
+impl<'a> IntoIterator for &'a Pool {
+    type Item = &'a char;
+    type IntoIter = Iter<'a, char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl Extend<char> for Pool {
     fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
         self.0.extend(iter)
@@ -48,7 +281,7 @@ impl FromStr for Pool {
     type Err = ParseCharError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Pool(s.chars().collect::<IndexSet<char>>()))
+        Ok(Pool(s.chars().collect::<CharSet>()))
     }
 }
 
@@ -64,10 +297,176 @@ impl Default for Pool {
     }
 }
 
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+/// Chars removed by [`Pool::remove_ambiguous`], which are easily confused with each other when
+/// read or typed from paper: zero and round letters `0 O o`, one and vertical strokes
+/// `1 I l |`, and the pairs `2 Z`, `5 S` and `8 B`
+pub const AMBIGUOUS_CHARS: &str = "0Oo1Il|2Z5S8B";
+const ASCII_SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
 impl Pool {
     /// Create new empty pool
     pub fn new() -> Self {
-        Pool(IndexSet::new())
+        Pool(CharSet::default())
+    }
+
+    /// Create new empty pool with room for at least `capacity` chars.
+    ///
+    /// Adding chars one by one reallocates the pool each time it is full. This only matters
+    /// when building pools of many thousands of chars, for example from large Unicode ranges.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// // The 20 992 CJK unified ideographs
+    /// let mut pool = Pool::with_capacity(20_992);
+    /// pool.extend_from_range('\u{4e00}'..='\u{9fff}');
+    ///
+    /// assert_eq!(pool.len(), 20_992);
+    /// assert!(pool.capacity() >= 20_992);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Pool(CharSet::with_capacity_and_hasher(
+            capacity,
+            Default::default(),
+        ))
+    }
+
+    /// Returns the number of chars the pool can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Reserve room for at least `additional` more chars. See [`Pool::with_capacity`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    /// Shrink the capacity of the pool as much as possible, to free the memory reserved by
+    /// [`Pool::with_capacity`] or [`Pool::reserve`] once the pool is built
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit()
+    }
+
+    /// Create pool with the 26 ASCII lowercase letters `abcdefghijklmnopqrstuvwxyz`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::lowercase().len(), 26)
+    /// ```
+    pub fn lowercase() -> Self {
+        LOWERCASE.chars().collect()
+    }
+
+    /// Create pool with the 26 ASCII uppercase letters `ABCDEFGHIJKLMNOPQRSTUVWXYZ`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::uppercase().len(), 26)
+    /// ```
+    pub fn uppercase() -> Self {
+        UPPERCASE.chars().collect()
+    }
+
+    /// Create pool with the 10 ASCII digits `0123456789`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::digits().len(), 10)
+    /// ```
+    pub fn digits() -> Self {
+        DIGITS.chars().collect()
+    }
+
+    /// Create pool with the 32 ASCII punctuation and symbol chars:
+    ///
+    /// ```text
+    /// !"#$%&'()*+,-./:;<=>?@[\]^_`{|}~
+    /// ```
+    ///
+    /// This set is frozen: it will not change between versions, so entropy computed from it
+    /// stays comparable.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::ascii_symbols().len(), 32)
+    /// ```
+    pub fn ascii_symbols() -> Self {
+        ASCII_SYMBOLS.chars().collect()
+    }
+
+    /// Create pool with the 62 ASCII letters and digits, in the order of
+    /// [`Pool::lowercase`], [`Pool::uppercase`] and [`Pool::digits`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::alphanumeric().len(), 62)
+    /// ```
+    pub fn alphanumeric() -> Self {
+        [LOWERCASE, UPPERCASE, DIGITS]
+            .iter()
+            .flat_map(|s| s.chars())
+            .collect()
+    }
+
+    /// Create pool with the 94 chars of [`Pool::lowercase`], [`Pool::uppercase`],
+    /// [`Pool::digits`] and [`Pool::ascii_symbols`], in this order
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::full().len(), 94)
+    /// ```
+    pub fn full() -> Self {
+        [LOWERCASE, UPPERCASE, DIGITS, ASCII_SYMBOLS]
+            .iter()
+            .flat_map(|s| s.chars())
+            .collect()
+    }
+
+    /// Create pool with the 95 printable ASCII chars, from space (U+0020) to `~` (U+007E),
+    /// in code point order
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::ascii_printable();
+    ///
+    /// assert_eq!(pool.len(), 95);
+    /// assert!(pool.contains(' '));
+    /// ```
+    pub fn ascii_printable() -> Self {
+        (' '..='~').collect()
+    }
+
+    /// Create pool with the 50 chars of [`Pool::alphanumeric`] which can't be mistaken for one
+    /// another when read off a screen and retyped:
+    ///
+    /// ```text
+    /// abcdefghijkmnpqrstuvwxyzACDEFGHJKLMNPQRTUVWXY34679
+    /// ```
+    ///
+    /// The 12 excluded chars come in look-alike groups: `0 O o` (zero and round letters),
+    /// `1 I l` (one and vertical strokes), `2 Z`, `5 S` and `8 B`. This is
+    /// [`Pool::alphanumeric`] with [`Pool::remove_ambiguous`] applied.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool = Pool::unambiguous_alphanumeric();
+    /// pool.extend_from_string("#%+=");
+    ///
+    /// assert_eq!(pool.len(), 54)
+    /// ```
+    pub fn unambiguous_alphanumeric() -> Self {
+        Pool::alphanumeric().without_ambiguous()
     }
 
     /// Return number of chars in the pool
@@ -85,11 +484,119 @@ impl Pool {
 
     /// Extracts all chars from string and adds them to the pool
     pub fn extend_from_string(&mut self, s: &str) -> &mut Self {
-        self.0.extend(s.chars().collect::<IndexSet<char>>());
+        self.0.extend(s.chars());
+
+        self
+    }
+
+    /// Create pool with every char of `range`, in code point order.
+    ///
+    /// Any range of chars is accepted (`'a'..='z'`, `'a'..'{'`, `..='~'`, ...). The surrogate
+    /// code points `U+D800..=U+DFFF` are not chars and are skipped when the range straddles
+    /// them. An empty or inverted range gives an empty pool.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::from_range('a'..='z'), Pool::lowercase());
+    /// assert_eq!(Pool::from_range('\u{3b1}'..='\u{3c9}').len(), 25);
+    ///
+    /// // Reversed ranges are empty, they are not swapped
+    /// assert!(Pool::from_range('z'..='a').is_empty());
+    /// ```
+    pub fn from_range<R: RangeBounds<char>>(range: R) -> Self {
+        let mut pool = Pool::new();
+        pool.extend_from_range(range);
+
+        pool
+    }
+
+    /// Adds every char of `range` to the pool. See [`Pool::from_range`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool = Pool::from_range('a'..='f');
+    /// pool.extend_from_range('0'..='9');
+    ///
+    /// assert_eq!(pool.to_string(), "abcdef0123456789")
+    /// ```
+    pub fn extend_from_range<R: RangeBounds<char>>(&mut self, range: R) -> &mut Self {
+        self.0.extend(chars_in(range));
 
         self
     }
 
+    /// Create pool with the chars of `range` for which `predicate` returns true, in code point
+    /// order.
+    ///
+    /// `predicate` is called once for every char of the range, so the cost grows with the size
+    /// of the range and not of the pool: the whole of Unicode is about 1.1 million chars. Use
+    /// [`Pool::from_char_filter_capped`] when the range comes from user input.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::from_char_filter('\0'..='\u{7f}', |ch| ch.is_ascii_hexdigit());
+    ///
+    /// assert_eq!(pool.to_string(), "0123456789ABCDEFabcdef")
+    /// ```
+    pub fn from_char_filter<R, F>(range: R, predicate: F) -> Self
+    where
+        R: RangeBounds<char>,
+        F: Fn(char) -> bool,
+    {
+        chars_in(range).filter(|ch| predicate(*ch)).collect()
+    }
+
+    /// Create pool like [`Pool::from_char_filter`], but stop as soon as more than `max_size`
+    /// chars match instead of building an enormous pool.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Pool, PassgenError};
+    /// let result = Pool::from_char_filter_capped(.., char::is_alphabetic, 1000);
+    ///
+    /// assert_eq!(result, Err(PassgenError::PoolTooLarge { max_size: 1000 }));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`PassgenError::PoolTooLarge`] if more than `max_size` chars match.
+    pub fn from_char_filter_capped<R, F>(
+        range: R,
+        predicate: F,
+        max_size: usize,
+    ) -> Result<Self, PassgenError>
+    where
+        R: RangeBounds<char>,
+        F: Fn(char) -> bool,
+    {
+        let mut pool = Pool::new();
+        for ch in chars_in(range).filter(|ch| predicate(*ch)) {
+            if pool.len() == max_size {
+                return Err(PassgenError::PoolTooLarge { max_size });
+            }
+            pool.0.insert(ch);
+        }
+
+        Ok(pool)
+    }
+
+    /// Create pool with the alphabetic chars of `range`, e.g. a Unicode block.
+    /// See [`Pool::from_char_filter`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let cyrillic = Pool::unicode_letters_in('\u{0400}'..='\u{04FF}');
+    ///
+    /// assert!(cyrillic.contains('Ж'));
+    /// assert!(!cyrillic.contains('\u{0483}'));
+    /// ```
+    pub fn unicode_letters_in<R: RangeBounds<char>>(range: R) -> Self {
+        Pool::from_char_filter(range, char::is_alphabetic)
+    }
+
     /// Returns true if pool contains no elements
     ///
     /// # Examples
@@ -103,11 +610,111 @@ impl Pool {
         self.0.is_empty()
     }
 
+    /// Returns true if every char of the pool is ASCII
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert!(Pool::alphanumeric().is_ascii());
+    /// assert!(!"abcé".parse::<Pool>().unwrap().is_ascii());
+    /// ```
+    pub fn is_ascii(&self) -> bool {
+        self.0.iter().all(char::is_ascii)
+    }
+
+    /// Returns the non-ASCII chars of the pool, in insertion order, for example to report
+    /// which chars make a pool unusable with [`generate_ascii_password`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "aé1€".parse().unwrap();
+    ///
+    /// assert_eq!(pool.non_ascii_chars(), ['é', '€']);
+    /// ```
+    pub fn non_ascii_chars(&self) -> Vec<char> {
+        self.0.iter().copied().filter(|ch| !ch.is_ascii()).collect()
+    }
+
+    /// Returns a copy of the pool without its non-ASCII chars
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "aé1€".parse().unwrap();
+    ///
+    /// assert_eq!(pool.to_ascii_lossy().to_string(), "a1");
+    /// ```
+    pub fn to_ascii_lossy(&self) -> Pool {
+        self.filtered(|ch| ch.is_ascii())
+    }
+
     /// Get char by index
     pub(crate) fn get(&self, index: usize) -> Option<&char> {
         self.0.get_index(index)
     }
 
+    /// Returns the char at `index`, in insertion order, or `None` if `index` is out of bounds.
+    /// This is the index used by the generation functions to pick chars.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::digits();
+    ///
+    /// assert_eq!(pool.char_at(3), Some('3'));
+    /// assert_eq!(pool.char_at(10), None);
+    /// ```
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.get(index).copied()
+    }
+
+    /// Returns the index of `ch` in the pool, or `None` if it is not in the pool
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::lowercase();
+    ///
+    /// assert_eq!(pool.index_of('c'), Some(2));
+    /// assert_eq!(pool.index_of('C'), None);
+    /// ```
+    pub fn index_of(&self, ch: char) -> Option<usize> {
+        self.0.get_index_of(&ch)
+    }
+
+    /// Returns the first char in insertion order, or `None` if the pool is empty. After
+    /// [`Pool::sort`] this is the smallest char.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool: Pool = "kza".parse().unwrap();
+    /// assert_eq!(pool.first(), Some(&'k'));
+    ///
+    /// pool.sort();
+    /// assert_eq!(pool.first(), Some(&'a'));
+    /// ```
+    pub fn first(&self) -> Option<&char> {
+        self.0.first()
+    }
+
+    /// Returns the last char in insertion order, or `None` if the pool is empty. After
+    /// [`Pool::sort`] this is the largest char.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool: Pool = "kza".parse().unwrap();
+    /// assert_eq!(pool.last(), Some(&'a'));
+    ///
+    /// pool.sort();
+    /// assert_eq!(pool.last(), Some(&'z'));
+    /// ```
+    pub fn last(&self) -> Option<&char> {
+        self.0.last()
+    }
+
     /// Check if char exists in the pool
     ///
     /// # Examples
@@ -121,343 +728,2703 @@ impl Pool {
         self.0.contains(&ch)
     }
 
-    /// Returns true if pool contains each char from the string `elements`
-    ///
-    /// # Examples
-    /// ```
-    /// # use libpassgen::Pool;
-    /// let pool: Pool = "ABCDEFG".parse().unwrap();
-    ///
-    /// assert!(pool.contains_all("DAG"))
-    /// ```
-    pub fn contains_all(&self, elements: &str) -> bool {
-        self.0
-            .is_superset(&elements.chars().collect::<IndexSet<char>>())
+    /// Returns true if pool contains each char from the string `elements`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "ABCDEFG".parse().unwrap();
+    ///
+    /// assert!(pool.contains_all("DAG"))
+    /// ```
+    pub fn contains_all(&self, elements: &str) -> bool {
+        self.0.is_superset(&elements.chars().collect::<CharSet>())
+    }
+
+    /// Insert char to pool.
+    /// If an equivalent char already exists in the pool, then the pool is not changed.
+    #[allow(dead_code)]
+    pub(crate) fn insert(&mut self, ch: char) {
+        self.0.insert(ch);
+    }
+
+    /// Returns iterator
+    pub fn iter(&self) -> Iter<'_, char> {
+        self.0.iter()
+    }
+
+    /// Returns iterator over copies of the chars, in insertion order
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "cab".parse().unwrap();
+    /// let upper: String = pool.chars().map(|ch| ch.to_ascii_uppercase()).collect();
+    ///
+    /// assert_eq!(upper, "CAB")
+    /// ```
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Returns the chars in a `Vec`, in insertion order, for example to pass them to C code
+    /// or to a custom serialization format
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "cab".parse().unwrap();
+    ///
+    /// assert_eq!(pool.to_vec(), ['c', 'a', 'b'])
+    /// ```
+    pub fn to_vec(&self) -> Vec<char> {
+        self.chars().collect()
+    }
+
+    /// Remove char from pool. Like a [Vec::swap_remove]
+    pub fn swap_remove(&mut self, ch: &char) -> bool {
+        self.0.swap_remove(ch)
+    }
+
+    /// Remove char from pool. Like a [Vec::remove]
+    pub fn shift_remove(&mut self, ch: &char) -> bool {
+        self.0.shift_remove(ch)
+    }
+
+    /// Remove all chars of the string `elements` from pool
+    pub fn remove_all(&mut self, elements: &str) {
+        elements.chars().for_each(|ch| {
+            self.swap_remove(&ch);
+        });
+    }
+
+    /// Keep only the chars for which `predicate` returns true, in their relative order
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool: Pool = "a b\tc".parse().unwrap();
+    /// pool.retain(|ch| ch.is_ascii_graphic()).extend_from_string("#");
+    ///
+    /// assert_eq!(pool.to_string(), "abc#");
+    ///
+    /// let mut pool: Pool = "z-é_9A".parse().unwrap();
+    /// pool.retain(|ch| ch.is_ascii_alphanumeric());
+    ///
+    /// assert_eq!(pool.to_string(), "z9A")
+    /// ```
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut predicate: F) -> &mut Self {
+        self.0.retain(|ch| predicate(*ch));
+
+        self
+    }
+
+    /// Returns a copy of the pool with only the chars for which `predicate` returns true.
+    /// See [`Pool::retain`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let vowels = Pool::lowercase().filtered(|ch| "aeiou".contains(ch));
+    ///
+    /// assert_eq!(vowels.to_string(), "aeiou")
+    /// ```
+    pub fn filtered<F: FnMut(char) -> bool>(&self, predicate: F) -> Pool {
+        let mut pool = self.clone();
+        pool.retain(predicate);
+
+        pool
+    }
+
+    /// Returns the number of chars for which `predicate` returns true
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::alphanumeric().count_matching(|ch| ch.is_ascii_digit()), 10)
+    /// ```
+    pub fn count_matching<F: FnMut(char) -> bool>(&self, mut predicate: F) -> usize {
+        self.0.iter().filter(|ch| predicate(**ch)).count()
+    }
+
+    /// Remove the chars of [`AMBIGUOUS_CHARS`], which are easily confused with each other when
+    /// read or typed from paper: `0 O o 1 I l | 2 Z 5 S 8 B`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool = Pool::digits();
+    /// pool.remove_ambiguous().extend_from_string("#$");
+    ///
+    /// assert_eq!(pool.to_string(), "34679#$")
+    /// ```
+    pub fn remove_ambiguous(&mut self) -> &mut Self {
+        self.0.retain(|ch| !AMBIGUOUS_CHARS.contains(*ch));
+
+        self
+    }
+
+    /// Returns a copy of the pool without ambiguous chars. See [`Pool::remove_ambiguous`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::uppercase().without_ambiguous();
+    ///
+    /// assert_eq!(pool.to_string(), "ACDEFGHJKLMNPQRTUVWXY")
+    /// ```
+    pub fn without_ambiguous(&self) -> Pool {
+        let mut pool = self.clone();
+        pool.remove_ambiguous();
+
+        pool
+    }
+
+    /// Returns a copy of the pool with each char mapped to its Unicode uppercase, keeping the
+    /// order of the first occurrence when several chars map to the same one.
+    ///
+    /// Chars whose uppercase is several chars, like `ß` which becomes `SS`, can't be kept as a
+    /// single char of the pool and are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "abcAß".parse().unwrap();
+    ///
+    /// assert_eq!(pool.to_uppercase().to_string(), "ABCß");
+    /// assert_eq!(Pool::lowercase().to_uppercase(), Pool::uppercase());
+    /// ```
+    pub fn to_uppercase(&self) -> Pool {
+        self.map_case(|ch| ch.to_uppercase())
+    }
+
+    /// Returns a copy of the pool with each char mapped to its Unicode lowercase. See
+    /// [`Pool::to_uppercase`]; multi-char mappings, like `İ` which becomes `i̇`, are left
+    /// unchanged too.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert_eq!(Pool::uppercase().to_lowercase(), Pool::lowercase());
+    /// ```
+    pub fn to_lowercase(&self) -> Pool {
+        self.map_case(|ch| ch.to_lowercase())
+    }
+
+    /// Returns a copy of the pool with the uppercase and lowercase of each char added after
+    /// its chars, so a lowercase pool gives both cases without listing them. Multi-char case
+    /// mappings add nothing, as with [`Pool::to_uppercase`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "abé".parse().unwrap();
+    ///
+    /// assert_eq!(pool.with_case_variants().to_string(), "abéABÉ");
+    /// assert_eq!(Pool::lowercase().with_case_variants().len(), 52);
+    /// ```
+    pub fn with_case_variants(&self) -> Pool {
+        self.union(&self.to_uppercase()).union(&self.to_lowercase())
+    }
+
+    fn map_case<I, F>(&self, convert: F) -> Pool
+    where
+        I: ExactSizeIterator<Item = char>,
+        F: Fn(char) -> I,
+    {
+        self.iter()
+            .map(|ch| {
+                let mut mapped = convert(*ch);
+                match mapped.len() {
+                    1 => mapped.next().unwrap(),
+                    _ => *ch,
+                }
+            })
+            .collect()
+    }
+
+    /// Sorts the chars in the pool
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// # use std::str::FromStr;
+    /// let mut pool = Pool::from_str("31524").unwrap();
+    /// pool.sort();
+    ///
+    /// assert_eq!(pool, Pool::from_str("12345").unwrap())
+    /// ```
+    pub fn sort(&mut self) {
+        self.0.sort()
+    }
+
+    /// Sorts the chars in the pool with the comparator `cmp`. The sort is stable.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool = Pool::digits();
+    /// pool.sort_by(|a, b| b.cmp(a));
+    ///
+    /// assert_eq!(pool.to_string(), "9876543210")
+    /// ```
+    pub fn sort_by<F: FnMut(&char, &char) -> Ordering>(&mut self, cmp: F) {
+        self.0.sort_by(cmp)
+    }
+
+    /// Sorts the chars in the pool by the key extracted with `key`. The sort is stable.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool: Pool = "a1B#".parse().unwrap();
+    /// pool.sort_by_key(|ch| (!ch.is_alphabetic(), !ch.is_ascii_digit()));
+    ///
+    /// assert_eq!(pool.to_string(), "aB1#")
+    /// ```
+    pub fn sort_by_key<K: Ord, F: FnMut(&char) -> K>(&mut self, mut key: F) {
+        self.0.sort_by(|a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Sorts the chars in the pool without preserving the order of equal elements, which is
+    /// faster for large pools. Chars are unique, so the result is the same as [`Pool::sort`].
+    pub fn sort_unstable(&mut self) {
+        self.0.sort_unstable()
+    }
+
+    /// Returns true if both pools have the same chars in the same order, unlike `==` which
+    /// ignores the order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool: Pool = "31524".parse().unwrap();
+    /// pool.sort();
+    ///
+    /// assert!(pool.eq_ordered(&"12345".parse().unwrap()));
+    /// assert!(!pool.eq_ordered(&"54321".parse().unwrap()));
+    /// ```
+    pub fn eq_ordered(&self, other: &Pool) -> bool {
+        self.0.iter().eq(other.0.iter())
+    }
+
+    /// Randomly permutes the chars in the pool with a Fisher-Yates shuffle. The result only
+    /// depends on `rng`, so a seeded generator gives a reproducible order.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use libpassgen::Pool;
+    /// let mut pool = Pool::digits();
+    /// pool.shuffle(&mut rand::thread_rng());
+    ///
+    /// assert_eq!(pool, Pool::digits())
+    /// # }
+    /// ```
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        for i in (1..self.0.len()).rev() {
+            self.0.swap_indices(i, rng.gen_range(0..=i));
+        }
+    }
+
+    /// Returns a pool of `n` distinct chars of this pool, picked uniformly at random without
+    /// replacement with a partial Fisher-Yates shuffle. The chars are in the order they were
+    /// picked.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use libpassgen::Pool;
+    /// let symbols = Pool::ascii_symbols().sample(20, &mut rand::thread_rng()).unwrap();
+    ///
+    /// assert_eq!(symbols.len(), 20);
+    /// assert!(symbols.is_subset(&Pool::ascii_symbols()));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`PassgenError::PoolTooSmall`] if `n` is greater than the number of chars.
+    pub fn sample<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Result<Pool, PassgenError> {
+        if n > self.len() {
+            return Err(PassgenError::PoolTooSmall {
+                len: self.len(),
+                required: n,
+            });
+        }
+
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        for i in 0..n {
+            indices.swap(i, rng.gen_range(i..self.len()));
+        }
+
+        Ok(indices[..n].iter().map(|idx| self[*idx]).collect())
+    }
+
+    /// Returns a copy of the pool with its chars randomly permuted. See [`Pool::shuffle`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::lowercase().shuffled(&mut rand::thread_rng());
+    ///
+    /// assert_eq!(pool, Pool::lowercase())
+    /// # }
+    /// ```
+    pub fn shuffled<R: Rng + ?Sized>(&self, rng: &mut R) -> Pool {
+        let mut pool = self.clone();
+        pool.shuffle(rng);
+
+        pool
+    }
+
+    /// Returns a new pool with the chars of `self` followed by the chars of `other`
+    /// which are not in `self`. Also available as `&a | &b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::digits().union(&"ABC".parse().unwrap());
+    ///
+    /// assert_eq!(pool.to_string(), "0123456789ABC")
+    /// ```
+    pub fn union(&self, other: &Pool) -> Pool {
+        Pool(self.0.union(&other.0).copied().collect())
+    }
+
+    /// Returns a new pool with the chars of `self` which are also in `other`, in the order
+    /// of `self`. Also available as `&a & &b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::alphanumeric().intersection(&"z0a".parse().unwrap());
+    ///
+    /// assert_eq!(pool.to_string(), "az0")
+    /// ```
+    pub fn intersection(&self, other: &Pool) -> Pool {
+        Pool(self.0.intersection(&other.0).copied().collect())
+    }
+
+    /// Returns a new pool with the chars of `self` which are not in `other`, in the order
+    /// of `self`. Also available as `&a - &b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::digits().difference(&"01".parse().unwrap());
+    ///
+    /// assert_eq!(pool.to_string(), "23456789")
+    /// ```
+    pub fn difference(&self, other: &Pool) -> Pool {
+        Pool(self.0.difference(&other.0).copied().collect())
+    }
+
+    /// Returns a new pool with the chars which are in exactly one of `self` and `other`: the
+    /// chars of `self` not in `other` in the order of `self`, followed by the chars of `other`
+    /// not in `self` in the order of `other`. Also available as `&a ^ &b`.
+    ///
+    /// Like the other set operations, the result only depends on the order of the operands,
+    /// so a password generated from it with a seeded RNG is reproducible.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool: Pool = "abc".parse().unwrap();
+    ///
+    /// assert_eq!(pool.symmetric_difference(&"dcb".parse().unwrap()).to_string(), "ad")
+    /// ```
+    pub fn symmetric_difference(&self, other: &Pool) -> Pool {
+        Pool(self.0.symmetric_difference(&other.0).copied().collect())
+    }
+
+    /// Returns true if every char of `self` is in `other`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert!(Pool::digits().is_subset(&Pool::alphanumeric()));
+    /// ```
+    pub fn is_subset(&self, other: &Pool) -> bool {
+        self.0.is_subset(&other.0)
+    }
+
+    /// Returns true if every char of `other` is in `self`
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// assert!(Pool::alphanumeric().is_superset(&Pool::digits()));
+    /// ```
+    pub fn is_superset(&self, other: &Pool) -> bool {
+        self.0.is_superset(&other.0)
+    }
+}
+
+impl BitOr<&Pool> for &Pool {
+    type Output = Pool;
+
+    fn bitor(self, other: &Pool) -> Pool {
+        self.union(other)
+    }
+}
+
+impl BitAnd<&Pool> for &Pool {
+    type Output = Pool;
+
+    fn bitand(self, other: &Pool) -> Pool {
+        self.intersection(other)
+    }
+}
+
+impl Sub<&Pool> for &Pool {
+    type Output = Pool;
+
+    fn sub(self, other: &Pool) -> Pool {
+        self.difference(other)
+    }
+}
+
+impl BitXor<&Pool> for &Pool {
+    type Output = Pool;
+
+    fn bitxor(self, other: &Pool) -> Pool {
+        self.symmetric_difference(other)
+    }
+}
+
+/// Generate random password.
+///
+/// Every char of `pool` is equally likely at each position, whatever the pool size: the
+/// index is drawn from a [`Uniform`] distribution, which rejects the values that would
+/// cause modulo bias. The distribution is built once per password and reused for every
+/// char.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password};
+/// let pool = "0123456789".parse().unwrap();
+/// let password = generate_password(&pool, 15);
+///
+/// assert_eq!(password.chars().count(), 15);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty. See [`try_generate_password`] for a non-panicking version,
+/// which rejects a zero `length`.
+#[cfg(feature = "std")]
+pub fn generate_password(pool: &Pool, length: usize) -> String {
+    unwrap_or_empty(try_generate_password(pool, length))
+}
+
+/// Generate random password, returning an error instead of panicking.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, PassgenError, try_generate_password};
+/// let pool = "0123456789".parse().unwrap();
+///
+/// assert_eq!(try_generate_password(&pool, 15).unwrap().chars().count(), 15);
+/// assert_eq!(try_generate_password(&Pool::new(), 15), Err(PassgenError::EmptyPool));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty and [`PassgenError::ZeroLength`]
+/// if `length` is zero.
+#[cfg(feature = "std")]
+pub fn try_generate_password(pool: &Pool, length: usize) -> Result<String, PassgenError> {
+    validate(pool, length)?;
+
+    Ok(sample_password(pool, length, &mut rand::thread_rng()))
+}
+
+/// Generate random password using the given random number generator.
+///
+/// Passing a seeded generator makes the output reproducible.
+///
+/// `rng` is not required to implement [`CryptoRng`], so that mock or
+/// seeded generators can be used in tests. Passwords meant for real use should come from a
+/// cryptographically secure generator such as [`rand::thread_rng`] or
+/// [`OsRng`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{Pool, generate_password_with_rng};
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let pool = "0123456789".parse().unwrap();
+/// let first = generate_password_with_rng(&pool, 15, &mut StdRng::seed_from_u64(42));
+/// let second = generate_password_with_rng(&pool, 15, &mut StdRng::seed_from_u64(42));
+///
+/// assert_eq!(first, second);
+/// # }
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_with_rng<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    rng: &mut R,
+) -> String {
+    unwrap_or_empty(validate(pool, length));
+
+    sample_password(pool, length, rng)
+}
+
+/// Generate random password using a random number generator behind a trait object, so that
+/// a boxed generator such as a hardware RNG can be stored and reused without making the
+/// caller generic.
+///
+/// The output is the same as [`generate_password_with_rng`] for the same generator state.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{Pool, generate_password_dyn};
+/// use rand::{rngs::StdRng, RngCore, SeedableRng};
+///
+/// let mut rng: Box<dyn RngCore> = Box::new(StdRng::seed_from_u64(42));
+/// let password = generate_password_dyn(&Pool::digits(), 15, rng.as_mut());
+///
+/// assert_eq!(password.len(), 15);
+/// # }
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_dyn(pool: &Pool, length: usize, rng: &mut dyn RngCore) -> String {
+    generate_password_with_rng(pool, length, rng)
+}
+
+/// Generate random password of a random length, picked uniformly between `min_len` and
+/// `max_len` included.
+///
+/// The varying length only adds a little entropy, at most `log2(max_len - min_len + 1)`
+/// bits, and an attacker can simply try the shorter lengths first. The strength of the
+/// password should be judged on `min_len` chars.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_range};
+/// let password = generate_password_range(&Pool::alphanumeric(), 12, 20);
+///
+/// assert!((12..=20).contains(&password.len()));
+/// ```
+///
+/// With a zero `min_len`, the password is empty when a zero length is picked.
+///
+/// # Panics
+/// Panics if `pool` is empty or `min_len` is greater than `max_len`. See
+/// [`try_generate_password_range`] for a non-panicking version, which rejects a zero
+/// `min_len`.
+#[cfg(feature = "std")]
+pub fn generate_password_range(pool: &Pool, min_len: usize, max_len: usize) -> String {
+    let mut rng = rand::thread_rng();
+
+    match try_generate_password_range_with_rng(pool, min_len, max_len, &mut rng) {
+        Err(PassgenError::ZeroLength) => {
+            let length = rng.gen_range(0..=max_len);
+            sample_password(pool, length, &mut rng)
+        }
+        result => result.unwrap_or_else(|e| panic!("{}", e)),
+    }
+}
+
+/// Generate random password of a random length between `min_len` and `max_len` included,
+/// returning an error instead of panicking. See [`generate_password_range`].
+///
+/// # Errors
+/// Returns [`PassgenError::InvalidLengthRange`] if `min_len` is greater than `max_len`,
+/// [`PassgenError::EmptyPool`] if `pool` is empty and [`PassgenError::ZeroLength`] if
+/// `min_len` is zero.
+#[cfg(feature = "std")]
+pub fn try_generate_password_range(
+    pool: &Pool,
+    min_len: usize,
+    max_len: usize,
+) -> Result<String, PassgenError> {
+    try_generate_password_range_with_rng(pool, min_len, max_len, &mut rand::thread_rng())
+}
+
+/// Generate random password of a random length between `min_len` and `max_len` included
+/// using the given random number generator. See [`generate_password_range`].
+///
+/// # Errors
+/// Same as [`try_generate_password_range`].
+pub fn try_generate_password_range_with_rng<R: Rng + ?Sized>(
+    pool: &Pool,
+    min_len: usize,
+    max_len: usize,
+    rng: &mut R,
+) -> Result<String, PassgenError> {
+    if min_len > max_len {
+        return Err(PassgenError::InvalidLengthRange {
+            min: min_len,
+            max: max_len,
+        });
+    }
+    validate(pool, min_len)?;
+    let length = rng.gen_range(min_len..=max_len);
+
+    Ok(sample_password(pool, length, rng))
+}
+
+/// Generate random password where every char is different, picking the chars of `pool`
+/// without replacement with a partial Fisher-Yates shuffle, so every ordered selection of
+/// `length` chars is equally likely. See [`Pool::sample`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{Pool, generate_distinct_password};
+/// let password = generate_distinct_password(&Pool::digits(), 10, &mut rand::thread_rng());
+/// let mut digits: Vec<char> = password.unwrap().chars().collect();
+/// digits.sort();
+///
+/// assert_eq!(digits.into_iter().collect::<String>(), "0123456789");
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero and [`PassgenError::PoolTooSmall`] if `length` is greater than the number
+/// of chars of `pool`.
+pub fn generate_distinct_password<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    rng: &mut R,
+) -> Result<String, PassgenError> {
+    validate(pool, length)?;
+
+    Ok(pool.sample(length, rng)?.iter().collect())
+}
+
+/// Generate random password from a pure ASCII pool.
+///
+/// This is faster than [`try_generate_password`] because the password is built directly as
+/// bytes instead of going through `char`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, PassgenError, generate_ascii_password};
+/// let password = generate_ascii_password(&Pool::alphanumeric(), 15).unwrap();
+///
+/// assert_eq!(password.len(), 15);
+/// assert_eq!(
+///     generate_ascii_password(&"abcé".parse().unwrap(), 15),
+///     Err(PassgenError::NonAsciiPool)
+/// );
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero and [`PassgenError::NonAsciiPool`] if `pool` contains a non-ASCII char.
+#[cfg(feature = "std")]
+pub fn generate_ascii_password(pool: &Pool, length: usize) -> Result<String, PassgenError> {
+    validate(pool, length)?;
+    if !pool.is_ascii() {
+        return Err(PassgenError::NonAsciiPool);
+    }
+
+    let bytes: Vec<u8> = pool.iter().map(|&ch| ch as u8).collect();
+    let range = Uniform::new(0, bytes.len());
+    let mut rng = rand::thread_rng();
+    let password: Vec<u8> = (0..length).map(|_| bytes[rng.sample(range)]).collect();
+
+    // SAFETY: every byte comes from an ASCII char, so `password` is valid UTF-8.
+    Ok(unsafe { String::from_utf8_unchecked(password) })
+}
+
+/// Generate random password into `out`, reusing its allocation.
+///
+/// The previous contents of `out` are discarded. Capacity is only reserved when `out` is
+/// too small, so generating many passwords into the same buffer does not allocate.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_into};
+/// let pool = "0123456789".parse().unwrap();
+/// let mut password = String::new();
+///
+/// for _ in 0..10 {
+///     generate_password_into(&pool, 15, &mut password);
+///     assert_eq!(password.len(), 15);
+/// }
+/// ```
+///
+/// `out` is only cleared when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_password_into(pool: &Pool, length: usize, out: &mut String) {
+    unwrap_or_empty(validate(pool, length));
+
+    out.clear();
+    out.reserve(max_char_len(pool) * length);
+    push_random_chars(out, pool, length, &mut rand::thread_rng());
+}
+
+/// Generate random password directly as UTF-8 bytes, for example to feed it to a hash or key
+/// derivation function without an intermediate `String`.
+///
+/// `length` counts chars: with chars outside of ASCII in `pool` the password has more than
+/// `length` bytes, up to 4 per char. The bytes are always valid UTF-8.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_bytes};
+/// let bytes = generate_password_bytes(&Pool::alphanumeric(), 32);
+///
+/// assert_eq!(bytes.len(), 32);
+///
+/// let bytes = generate_password_bytes(&"é€".parse().unwrap(), 32);
+///
+/// assert!(bytes.len() > 32);
+/// assert_eq!(std::str::from_utf8(&bytes).unwrap().chars().count(), 32);
+/// ```
+///
+/// No bytes are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_password_bytes(pool: &Pool, length: usize) -> Vec<u8> {
+    unwrap_or_empty(validate(pool, length));
+
+    let mut bytes = Vec::with_capacity(max_char_len(pool) * length);
+    let mut buf = [0; 4];
+    for ch in random_chars(pool, length, &mut rand::thread_rng()) {
+        bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+    }
+
+    bytes
+}
+
+/// Write random password to `w`, without allocating.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, write_password};
+/// use std::fmt::Write;
+///
+/// let pool = "0123456789".parse().unwrap();
+/// let mut line = String::from("password: ");
+/// write_password(&pool, 15, &mut line).unwrap();
+///
+/// assert_eq!(line.len(), 25);
+/// ```
+///
+/// # Errors
+/// Returns an error if writing to `w` fails.
+///
+/// Nothing is written when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn write_password(pool: &Pool, length: usize, w: &mut impl fmt::Write) -> fmt::Result {
+    unwrap_or_empty(validate(pool, length));
+
+    random_chars(pool, length, &mut rand::thread_rng()).try_for_each(|ch| w.write_char(ch))
+}
+
+/// Generate random password with a cryptographically secure random number generator.
+///
+/// Unlike [`generate_password_with_rng`], the [`CryptoRng`] bound makes it impossible to pass
+/// a generator that is not marked as cryptographically secure, so this is the entry point to
+/// audit for security-sensitive code.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{Pool, generate_password_secure};
+/// use rand::rngs::OsRng;
+///
+/// let pool = "0123456789".parse().unwrap();
+/// let password = generate_password_secure(&pool, 15, &mut OsRng);
+///
+/// assert_eq!(password.chars().count(), 15);
+/// # }
+/// ```
+///
+/// A generator which is not a [`CryptoRng`] is rejected at compile time:
+/// ```compile_fail
+/// # use libpassgen::{Pool, generate_password_secure};
+/// use rand::rngs::mock::StepRng;
+///
+/// let pool = "0123456789".parse().unwrap();
+/// let password = generate_password_secure(&pool, 15, &mut StepRng::new(0, 1));
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_secure<R: Rng + CryptoRng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    rng: &mut R,
+) -> String {
+    generate_password_with_rng(pool, length, rng)
+}
+
+/// Generate random password using the operating system's random number generator
+/// ([`OsRng`]).
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_os};
+/// let pool = "0123456789".parse().unwrap();
+/// let password = generate_password_os(&pool, 15);
+///
+/// assert_eq!(password.chars().count(), 15);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_password_os(pool: &Pool, length: usize) -> String {
+    generate_password_secure(pool, length, &mut OsRng)
+}
+
+/// Generate multiple random passwords.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_n_passwords};
+/// let pool = "0123456789".parse().unwrap();
+/// let vec_passwords = generate_n_passwords(&pool, 15,5);
+///
+/// assert!(!vec_passwords[2].is_empty());
+/// ```
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty. See [`try_generate_n_passwords`] for a non-panicking version,
+/// which rejects a zero `length`.
+#[cfg(feature = "std")]
+pub fn generate_n_passwords(pool: &Pool, length: usize, count: usize) -> Vec<String> {
+    match try_generate_n_passwords(pool, length, count) {
+        Err(PassgenError::ZeroLength) => vec![String::new(); count],
+        result => result.unwrap_or_else(|e| panic!("{}", e)),
+    }
+}
+
+/// Generate multiple random passwords, returning an error instead of panicking.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, PassgenError, try_generate_n_passwords};
+/// let pool = "0123456789".parse().unwrap();
+///
+/// assert_eq!(try_generate_n_passwords(&pool, 15, 5).unwrap().len(), 5);
+/// assert_eq!(try_generate_n_passwords(&pool, 0, 5), Err(PassgenError::ZeroLength));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty and [`PassgenError::ZeroLength`]
+/// if `length` is zero.
+#[cfg(feature = "std")]
+pub fn try_generate_n_passwords(
+    pool: &Pool,
+    length: usize,
+    count: usize,
+) -> Result<Vec<String>, PassgenError> {
+    validate(pool, length)?;
+
+    Ok(password_iter(pool, length).take(count).collect())
+}
+
+/// Returns an infinite iterator of random passwords, generated lazily with
+/// [`rand::thread_rng`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, password_iter};
+/// let pool = "0123456789".parse().unwrap();
+/// let password = password_iter(&pool, 8).find(|p| !p.starts_with('0')).unwrap();
+///
+/// assert_eq!(password.len(), 8);
+/// ```
+///
+/// The passwords are empty when `length` is zero.
+///
+/// # Panics
+/// Panics when called, not when iterated, if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn password_iter(pool: &Pool, length: usize) -> impl Iterator<Item = String> + '_ {
+    password_iter_with_rng(pool, length, rand::thread_rng())
+}
+
+/// Generate random password and return it with its entropy in bits, computed with
+/// [`calculate_entropy`] from the number of chars of `pool`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_with_entropy};
+/// let (password, entropy) = generate_password_with_entropy(&Pool::digits(), 12);
+///
+/// assert_eq!(password.len(), 12);
+/// assert!((entropy - 39.86).abs() < 0.01);
+/// ```
+///
+/// An empty password with an entropy of 0 bits is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_password_with_entropy(pool: &Pool, length: usize) -> (String, f64) {
+    (
+        generate_password(pool, length),
+        calculate_entropy(length, pool.len()),
+    )
+}
+
+/// Generate multiple random passwords, each returned with its entropy in bits. See
+/// [`generate_password_with_entropy`].
+///
+/// Empty passwords with an entropy of 0 bits are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_n_passwords_with_entropy(
+    pool: &Pool,
+    length: usize,
+    count: usize,
+) -> Vec<(String, f64)> {
+    let entropy = calculate_entropy(length, pool.len());
+
+    password_iter(pool, length)
+        .take(count)
+        .map(|password| (password, entropy))
+        .collect()
+}
+
+/// Generate random password with at least `min_entropy` bits of entropy, using the shortest
+/// length given by [`calculate_length`] (and at least one char).
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, calculate_entropy, generate_for_entropy};
+/// let pool = Pool::alphanumeric();
+/// let password = generate_for_entropy(&pool, 128.0);
+///
+/// assert_eq!(password.len(), 22);
+/// assert!(calculate_entropy(password.len(), pool.len()) >= 128.0);
+/// ```
+///
+/// # Panics
+/// Panics if `pool` has fewer than 2 chars or if `min_entropy` is not finite. See
+/// [`try_generate_for_entropy`] for a non-panicking version.
+#[cfg(feature = "std")]
+pub fn generate_for_entropy(pool: &Pool, min_entropy: f64) -> String {
+    try_generate_for_entropy(pool, min_entropy).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Generate random password with at least `min_entropy` bits of entropy, returning an error
+/// instead of panicking.
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::PoolTooSmall`]
+/// if it has a single char, which gives no entropy whatever the length,
+/// [`PassgenError::InvalidEntropy`] if `min_entropy` is NaN or infinite and
+/// [`PassgenError::EntropyTooLarge`] if the password would need more bytes than can be
+/// allocated.
+#[cfg(feature = "std")]
+pub fn try_generate_for_entropy(pool: &Pool, min_entropy: f64) -> Result<String, PassgenError> {
+    if pool.is_empty() {
+        return Err(PassgenError::EmptyPool);
+    }
+    if pool.len() < 2 {
+        return Err(PassgenError::PoolTooSmall {
+            len: pool.len(),
+            required: 2,
+        });
+    }
+    if !min_entropy.is_finite() {
+        return Err(PassgenError::InvalidEntropy);
+    }
+
+    let length = calculate_length(min_entropy, pool.len() as f64).max(1.0);
+    if length >= usize::MAX as f64
+        || max_char_len(pool)
+            .checked_mul(length as usize)
+            .filter(|bytes| *bytes <= isize::MAX as usize)
+            .is_none()
+    {
+        return Err(PassgenError::EntropyTooLarge);
+    }
+
+    try_generate_password(pool, length as usize)
+}
+
+/// Generate multiple random passwords using the given random number generator.
+///
+/// As with [`generate_password_with_rng`], `rng` does not have to be a
+/// [`CryptoRng`]; only use such generators for tests.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{Pool, generate_n_passwords_with_rng};
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let pool = "0123456789".parse().unwrap();
+/// let first = generate_n_passwords_with_rng(&pool, 15, 5, &mut StdRng::seed_from_u64(42));
+/// let second = generate_n_passwords_with_rng(&pool, 15, 5, &mut StdRng::seed_from_u64(42));
+///
+/// assert_eq!(first, second);
+/// # }
+/// ```
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_n_passwords_with_rng<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    count: usize,
+    rng: &mut R,
+) -> Vec<String> {
+    password_iter_with_rng(pool, length, rng)
+        .take(count)
+        .collect()
+}
+
+/// Infinite iterator of passwords generated with `rng`, empty when `length` is zero
+///
+/// # Panics
+/// Panics if `pool` is empty.
+fn password_iter_with_rng<'a, R: Rng + 'a>(
+    pool: &'a Pool,
+    length: usize,
+    mut rng: R,
+) -> impl Iterator<Item = String> + 'a {
+    unwrap_or_empty(validate(pool, length));
+
+    let capacity = max_char_len(pool) * length;
+    core::iter::repeat_with(move || {
+        let mut password = String::with_capacity(capacity);
+        push_random_chars(&mut password, pool, length, &mut rng);
+
+        password
+    })
+}
+
+/// Check that a password of `length` chars can be generated from `pool`
+fn validate(pool: &Pool, length: usize) -> Result<(), PassgenError> {
+    if pool.is_empty() {
+        return Err(PassgenError::EmptyPool);
+    }
+    if length == 0 {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    Ok(())
+}
+
+/// Unwrap `result` for the panicking functions, which return an empty password instead of
+/// panicking when the length is zero
+pub(crate) fn unwrap_or_empty<T: Default>(result: Result<T, PassgenError>) -> T {
+    match result {
+        Err(PassgenError::ZeroLength) => T::default(),
+        result => result.unwrap_or_else(|e| panic!("{}", e)),
+    }
+}
+
+/// Pick `length` random chars from `pool`. The pool must not be empty.
+fn sample_password<R: Rng + ?Sized>(pool: &Pool, length: usize, rng: &mut R) -> String {
+    let mut password = String::with_capacity(max_char_len(pool) * length);
+    push_random_chars(&mut password, pool, length, rng);
+
+    password
+}
+
+/// Append `length` random chars from `pool` to `buf`. The pool must not be empty.
+fn push_random_chars<R: Rng + ?Sized>(buf: &mut String, pool: &Pool, length: usize, rng: &mut R) {
+    buf.extend(random_chars(pool, length, rng));
+}
+
+/// Iterator over `length` random chars from `pool`, all drawn with the same [`Uniform`]
+/// distribution. The pool must not be empty.
+fn random_chars<'a, R: Rng + ?Sized>(
+    pool: &'a Pool,
+    length: usize,
+    rng: &'a mut R,
+) -> impl Iterator<Item = char> + 'a {
+    let range = Uniform::new(0, pool.len());
+    (0..length).map(move |_| *pool.get(rng.sample(range)).unwrap())
+}
+
+/// Chars of `range` in code point order, skipping the surrogate code points
+fn chars_in<R: RangeBounds<char>>(range: R) -> impl Iterator<Item = char> {
+    let start = match range.start_bound() {
+        Bound::Included(ch) => *ch as u32,
+        Bound::Excluded(ch) => *ch as u32 + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(ch) => Some(*ch as u32),
+        Bound::Excluded(ch) => (*ch as u32).checked_sub(1),
+        Bound::Unbounded => Some(char::MAX as u32),
+    };
+
+    end.into_iter()
+        .flat_map(move |end| start..=end)
+        .filter_map(char::from_u32)
+}
+
+/// Number of bytes of the widest char of `pool` once encoded in UTF-8
+fn max_char_len(pool: &Pool) -> usize {
+    pool.iter().map(|ch| ch.len_utf8()).max().unwrap_or(0)
+}
+
+/// Calculates entropy.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::calculate_entropy;
+///
+/// assert_eq!(calculate_entropy(12, 64), 72_f64);
+/// ```
+#[cfg(feature = "std")]
+pub fn calculate_entropy(length: usize, pool_size: usize) -> f64 {
+    length as f64 * (pool_size as f64).log2()
+}
+
+/// Calculates the minimum password length required to obtain a given entropy.
+///
+/// The result is NaN or infinite when `pool_size` is 1 or less, since such a pool gives no
+/// entropy. See [`try_calculate_length`] for a checked version.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::calculate_length;
+///
+/// assert_eq!(calculate_length(128_f64, 64_f64), 22_f64);
+/// ```
+#[cfg(feature = "std")]
+pub fn calculate_length(entropy: f64, pool_size: f64) -> f64 {
+    (entropy / pool_size.log2()).ceil()
+}
+
+/// Calculates the minimum password length required to obtain a given entropy, returning an
+/// error instead of NaN or infinity for degenerate inputs. See [`calculate_length`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{PassgenError, try_calculate_length};
+/// assert_eq!(try_calculate_length(128_f64, 64_f64), Ok(22_f64));
+/// assert_eq!(
+///     try_calculate_length(128_f64, 1_f64),
+///     Err(PassgenError::PoolTooSmall { len: 1, required: 2 })
+/// );
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::InvalidEntropy`] if `entropy` is negative, NaN or infinite and
+/// [`PassgenError::PoolTooSmall`] if `pool_size` is 1 or less, or NaN. The `len` of the
+/// error is `pool_size` rounded down.
+#[cfg(feature = "std")]
+pub fn try_calculate_length(entropy: f64, pool_size: f64) -> Result<f64, PassgenError> {
+    if !entropy.is_finite() || entropy < 0.0 {
+        return Err(PassgenError::InvalidEntropy);
+    }
+    if pool_size.is_nan() || pool_size <= 1.0 {
+        return Err(PassgenError::PoolTooSmall {
+            len: pool_size as usize,
+            required: 2,
+        });
+    }
+
+    Ok(calculate_length(entropy, pool_size))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn pool_deref_mut() {
+        let mut pool = Pool::from_str("12345").unwrap();
+        *pool = "abcde".chars().collect::<IndexSet<char>>();
+
+        assert_eq!(*pool, "abcde".chars().collect::<IndexSet<char>>())
+    }
+
+    #[test]
+    fn pool_deref() {
+        let pool = Pool::from_str("12345").unwrap();
+
+        assert_eq!(*pool, "12345".chars().collect::<IndexSet<char>>())
+    }
+
+    #[test]
+    fn pool_sort() {
+        let mut pool = Pool::from_str("31524").unwrap();
+        pool.sort();
+
+        assert_eq!(pool, Pool::from_str("12345").unwrap())
+    }
+
+    #[test]
+    fn pool_extend() {
+        let mut pool = Pool::from_str("abc").unwrap();
+        pool.extend(vec!['d', 'e']);
+
+        assert_eq!(pool, Pool::from_str("abcde").unwrap())
+    }
+
+    #[test]
+    fn pool_from_iter() {
+        let iter = vec!['a', 'b', 'c'].into_iter();
+
+        assert_eq!(iter.collect::<Pool>(), Pool::from_str("abc").unwrap());
+    }
+
+    #[test]
+    fn pool_remove_all() {
+        let mut pool: Pool = "abcde".parse().unwrap();
+        pool.remove_all("ace");
+
+        assert_eq!(pool, "bd".parse::<Pool>().unwrap());
+    }
+
+    #[test]
+    fn pool_swap_remove() {
+        let mut pool: Pool = "abcdefz".parse().unwrap();
+
+        assert!(pool.swap_remove(&'b'));
+        assert_eq!(pool.get(1), Some(&'z'));
+        assert_eq!(pool.get(6), None);
+    }
+
+    #[test]
+    fn pool_shift_remove() {
+        let mut pool: Pool = "abcdefz".parse().unwrap();
+
+        assert!(pool.shift_remove(&'b'));
+        assert_eq!(pool.get(1), Some(&'c'));
+        assert_eq!(pool.get(6), None);
+    }
+
+    #[test]
+    fn pool_iter() {
+        let pool: Pool = "abcdefz".parse().unwrap();
+        let mut iter = pool.iter();
+
+        assert_eq!(iter.next(), Some(&'a'));
+        assert_eq!(iter.next(), Some(&'b'));
+        assert_eq!(iter.last(), Some(&'z'));
+    }
+
+    #[test]
+    fn pool_display() {
+        let pool: Pool = "0123456789".parse().unwrap();
+
+        assert_eq!(pool.to_string(), "0123456789".to_owned());
+    }
+
+    #[test]
+    fn pool_contains_all() {
+        let pool: Pool = "0123456789".parse().unwrap();
+
+        assert!(pool.contains_all("2357"));
+    }
+
+    #[test]
+    fn pool_contains_all_assert_false() {
+        let pool: Pool = "0123456789".parse().unwrap();
+
+        assert!(!pool.contains_all("0123F"));
+    }
+
+    #[test]
+    fn pool_contains() {
+        let pool: Pool = "0123456789".parse().unwrap();
+
+        assert!(pool.contains('5'));
+    }
+
+    #[test]
+    fn pool_contains_assert_false() {
+        let pool: Pool = "0123456789".parse().unwrap();
+
+        assert!(!pool.contains('A'));
+    }
+
+    #[test]
+    fn pool_get() {
+        let pool: Pool = "ABCD".parse().unwrap();
+
+        assert_eq!(pool.get(0), Some(&'A'))
+    }
+
+    #[test]
+    fn pool_is_empty() {
+        let pool = Pool::new();
+
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn pool_is_empty_assert_false() {
+        let pool = Pool::from_str("0123456789").unwrap();
+
+        assert!(!pool.is_empty());
+    }
+
+    #[test]
+    fn pool_len() {
+        let pool: Pool = "0123456789".parse().unwrap();
+
+        assert_eq!(pool.len(), 10)
+    }
+
+    #[test]
+    fn pool_insert() {
+        let mut pool = "ABC".parse::<Pool>().unwrap();
+        pool.insert('D');
+
+        assert_eq!(pool, "ABCD".parse::<Pool>().unwrap())
+    }
+
+    #[test]
+    fn pool_extend_from_string() {
+        let mut pool = "ABC".parse::<Pool>().unwrap();
+        let mut other_pool = pool.clone();
+
+        other_pool.insert('D');
+        pool.extend_from_string("D");
+
+        assert_eq!(other_pool, pool)
+    }
+
+    #[test]
+    fn pool_from_string() {
+        let indexset: IndexSet<_> = "0123456789".chars().collect();
+
+        assert_eq!(Pool(indexset), "0123456789".to_owned().parse().unwrap())
+    }
+
+    #[test]
+    fn pool_from_str() {
+        let indexset: IndexSet<_> = "0123456789".chars().collect();
+
+        assert_eq!(Pool(indexset), "0123456789".parse().unwrap())
+    }
+
+    #[test]
+    fn pool_lowercase() {
+        let pool = Pool::lowercase();
+
+        assert_eq!(pool.to_string(), "abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(pool.len(), 26);
+    }
+
+    #[test]
+    fn pool_uppercase() {
+        let pool = Pool::uppercase();
+
+        assert_eq!(pool.to_string(), "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+        assert_eq!(pool.len(), 26);
+    }
+
+    #[test]
+    fn pool_digits() {
+        let pool = Pool::digits();
+
+        assert_eq!(pool.to_string(), "0123456789");
+        assert_eq!(pool.len(), 10);
+    }
+
+    #[test]
+    fn pool_ascii_symbols() {
+        let pool = Pool::ascii_symbols();
+
+        assert_eq!(pool.to_string(), r##"!"#$%&'()*+,-./:;<=>?@[\]^_`{|}~"##);
+        assert_eq!(pool.len(), 32);
+        assert!(pool.iter().all(|ch| ch.is_ascii_punctuation()));
+    }
+
+    #[test]
+    fn pool_alphanumeric() {
+        let pool = Pool::alphanumeric();
+
+        assert_eq!(
+            pool.to_string(),
+            "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+        );
+        assert_eq!(pool.len(), 62);
+    }
+
+    #[test]
+    fn pool_full() {
+        let pool = Pool::full();
+
+        assert_eq!(
+            pool.to_string(),
+            "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789\
+             !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~"
+        );
+        assert_eq!(pool.len(), 94);
+        assert!(pool.iter().all(|ch| ch.is_ascii_graphic()));
+    }
+
+    #[test]
+    fn pool_ascii_printable() {
+        let pool = Pool::ascii_printable();
+
+        assert_eq!(
+            pool.to_string(),
+            " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`\
+             abcdefghijklmnopqrstuvwxyz{|}~"
+        );
+        assert_eq!(pool.len(), 95);
+    }
+
+    #[test]
+    fn pool_ascii_printable_is_full_with_space() {
+        let mut full = Pool::full();
+        full.insert(' ');
+
+        assert_eq!(Pool::ascii_printable(), full);
+    }
+
+    #[test]
+    fn pool_from_range_ascii() {
+        assert_eq!(Pool::from_range('a'..='z').to_string(), LOWERCASE);
+        assert_eq!(Pool::from_range('0'..':').to_string(), DIGITS);
+        assert_eq!(Pool::from_range(..='~'), Pool::from_range('\0'..='~'));
+    }
+
+    #[test]
+    fn pool_from_range_multibyte() {
+        let pool = Pool::from_range('\u{3b1}'..='\u{3c9}');
+
+        assert_eq!(pool.len(), 25);
+        assert!(pool.contains('\u{3c2}'));
+        assert!(pool.iter().all(|ch| ch.len_utf8() == 2));
+    }
+
+    #[test]
+    fn pool_from_range_skips_surrogates() {
+        let pool = Pool::from_range('\u{D700}'..='\u{E080}');
+
+        assert_eq!(pool.len(), 0xE081 - 0xD700 - 0x800);
+        assert!(pool.contains('\u{D7FF}'));
+        assert!(pool.contains('\u{E000}'));
+    }
+
+    #[test]
+    fn pool_from_range_latin() {
+        let pool = Pool::from_range('\u{C0}'..='\u{24F}');
+
+        assert_eq!(pool.len(), 0x250 - 0xC0);
+        assert_eq!(pool.char_at(0), Some('À'));
+        assert_eq!(pool.iter().last(), Some(&'\u{24F}'));
+    }
+
+    #[test]
+    fn pool_from_range_empty() {
+        assert!(Pool::from_range('z'..='a').is_empty());
+        assert!(Pool::from_range('a'..'a').is_empty());
+        assert!(Pool::from_range(..'\0').is_empty());
+    }
+
+    #[test]
+    fn pool_extend_from_range_keeps_existing() {
+        let mut pool: Pool = "xa".parse().unwrap();
+        pool.extend_from_range('a'..='c');
+
+        assert_eq!(pool.to_string(), "xabc");
+    }
+
+    #[test]
+    fn pool_from_char_filter() {
+        let pool = Pool::from_char_filter('a'..='z', |ch| "aeiouy".contains(ch));
+
+        assert_eq!(pool.to_string(), "aeiouy");
+        assert!(Pool::from_char_filter('a'..='z', |_| false).is_empty());
+    }
+
+    #[test]
+    fn pool_from_char_filter_capped() {
+        let digits = Pool::from_char_filter_capped('0'..='9', |_| true, 10);
+
+        assert_eq!(digits, Ok(Pool::digits()));
+        assert_eq!(
+            Pool::from_char_filter_capped('0'..='9', |_| true, 9),
+            Err(PassgenError::PoolTooLarge { max_size: 9 })
+        );
+    }
+
+    #[test]
+    fn pool_unicode_letters_in_cyrillic() {
+        let pool = Pool::unicode_letters_in('\u{0400}'..='\u{04FF}');
+
+        assert!(pool.contains('Д'));
+        assert!(pool.contains('я'));
+        assert!(pool.contains('Ѐ'));
+        assert!(!pool.contains('a'));
+        assert!(!pool.contains('\u{0482}'));
+        assert!(pool.iter().all(|ch| ch.is_alphabetic()));
+    }
+
+    #[test]
+    fn pool_to_uppercase_deduplicates() {
+        let pool: Pool = "aAbB1".parse().unwrap();
+
+        assert_eq!(pool.to_uppercase().to_string(), "AB1");
+        assert_eq!(pool.to_lowercase().to_string(), "ab1");
+    }
+
+    #[test]
+    fn pool_case_multi_char_mappings_unchanged() {
+        let pool: Pool = "ßİ".parse().unwrap();
+
+        assert_eq!(pool.to_uppercase().to_string(), "ßİ");
+        assert_eq!(pool.to_lowercase().to_string(), "ßİ");
+    }
+
+    #[test]
+    fn pool_with_case_variants() {
+        let pool: Pool = "aBß1İ".parse().unwrap();
+
+        assert_eq!(pool.with_case_variants().to_string(), "aBß1İAb");
+        assert_eq!(Pool::uppercase().with_case_variants().len(), 52);
+    }
+
+    #[test]
+    fn pool_case_unicode() {
+        let pool: Pool = "αβγ".parse().unwrap();
+
+        assert_eq!(pool.to_uppercase().to_string(), "ΑΒΓ");
+        assert_eq!(pool.to_uppercase().to_lowercase(), pool);
+    }
+
+    #[test]
+    fn pool_retain_keeps_order() {
+        let mut pool: Pool = "z1y2x3".parse().unwrap();
+        pool.retain(|ch| ch.is_ascii_digit());
+
+        assert_eq!(pool.to_string(), "123");
+    }
+
+    #[test]
+    fn pool_retain_removes_everything() {
+        let mut pool = Pool::alphanumeric();
+
+        assert!(pool.retain(|_| false).is_empty());
+    }
+
+    #[test]
+    fn pool_retain_removes_nothing() {
+        let mut pool = Pool::alphanumeric();
+        pool.retain(|_| true);
+
+        assert_eq!(pool.to_string(), Pool::alphanumeric().to_string());
+    }
+
+    #[test]
+    fn pool_filtered_leaves_original() {
+        let pool = Pool::alphanumeric();
+        let upper = pool.filtered(|ch| ch.is_ascii_uppercase());
+
+        assert_eq!(upper, Pool::uppercase());
+        assert_eq!(pool.len(), 62);
+    }
+
+    #[test]
+    fn pool_non_ascii_chars_mixed_pool() {
+        let pool: Pool = "aß1é~€\u{7f}😀".parse().unwrap();
+
+        assert_eq!(pool.non_ascii_chars(), ['ß', 'é', '€', '😀']);
+        assert_eq!(pool.to_ascii_lossy().to_string(), "a1~\u{7f}");
+        assert!(pool.to_ascii_lossy().is_ascii());
+        assert!(Pool::alphanumeric().non_ascii_chars().is_empty());
+    }
+
+    #[test]
+    fn pool_with_capacity_same_behavior() {
+        let mut pool = Pool::with_capacity(10_000);
+        let capacity = pool.capacity();
+        pool.extend_from_range('\u{1000}'..'\u{3710}');
+
+        assert!(capacity >= 10_000);
+        assert_eq!(pool.capacity(), capacity);
+        assert_eq!(pool, Pool::from_range('\u{1000}'..'\u{3710}'));
+        assert_eq!(pool.len(), 10_000);
+
+        pool.retain(|ch| ch < '\u{1010}');
+        pool.shrink_to_fit();
+        assert!(pool.capacity() < capacity);
+        pool.reserve(100);
+        assert!(pool.capacity() >= 116);
+        assert_eq!(pool, Pool::from_range('\u{1000}'..'\u{1010}'));
+    }
+
+    #[test]
+    fn pool_to_vec_keeps_order() {
+        let pool: Pool = "zé9😀".parse().unwrap();
+
+        assert_eq!(pool.to_vec(), ['z', 'é', '9', '😀']);
+        assert_eq!(pool.to_vec().into_iter().collect::<Pool>(), pool);
+        assert!(Pool::new().to_vec().is_empty());
+    }
+
+    #[test]
+    fn pool_into_iter_keeps_order() {
+        let pool: Pool = "zay9".parse().unwrap();
+        let chars: Vec<char> = pool.into_iter().collect();
+
+        assert_eq!(chars, ['z', 'a', 'y', '9']);
+    }
+
+    #[test]
+    fn pool_ref_into_iter_keeps_order() {
+        let pool: Pool = "zay9".parse().unwrap();
+        let mut chars = Vec::new();
+        for ch in &pool {
+            chars.push(*ch);
+        }
+
+        assert_eq!(chars, ['z', 'a', 'y', '9']);
+        assert_eq!(pool.chars().collect::<Vec<char>>(), chars);
+        assert!(chars
+            .iter()
+            .enumerate()
+            .all(|(idx, ch)| pool.get(idx) == Some(ch)));
+    }
+
+    #[test]
+    fn pool_into_iter_collect_round_trip() {
+        let pool = Pool::alphanumeric();
+
+        assert_eq!(
+            pool.clone().into_iter().collect::<Pool>().to_string(),
+            pool.to_string()
+        );
+    }
+
+    #[test]
+    fn pool_into_iter_by_value_in_for_loop() {
+        let pool: Pool = "aé€😀".parse().unwrap();
+        let mut rebuilt = Pool::new();
+        for ch in pool.clone() {
+            rebuilt.insert(ch);
+        }
+
+        assert_eq!(rebuilt, pool);
+        assert_eq!(pool.clone().into_iter().collect::<Pool>(), pool);
+    }
+
+    #[test]
+    fn pool_char_at_and_index() {
+        let pool: Pool = "zay".parse().unwrap();
+
+        assert_eq!(pool.char_at(0), Some('z'));
+        assert_eq!(pool[2], 'y');
+        assert_eq!(pool.index_of('a'), Some(1));
+        assert!((0..pool.len()).all(|idx| pool.index_of(pool[idx]) == Some(idx)));
+    }
+
+    #[test]
+    fn pool_first_and_last() {
+        let mut pool = Pool::ascii_symbols();
+        pool.extend_from_string("é0");
+        pool.sort();
+
+        assert_eq!(pool.first(), Some(&'!'));
+        assert_eq!(pool.last(), Some(&'é'));
+        assert_eq!(Pool::new().first(), None);
+        assert_eq!(Pool::new().last(), None);
+
+        let single: Pool = "x".parse().unwrap();
+        assert_eq!(single.first(), single.last());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn pool_index_out_of_bounds() {
+        let pool: Pool = "zay".parse().unwrap();
+
+        let _ = pool[3];
+    }
+
+    #[test]
+    fn pool_indices_after_swap_remove() {
+        let mut pool: Pool = "abcd".parse().unwrap();
+        pool.swap_remove(&'a');
+
+        assert_eq!(pool.to_string(), "dbc");
+        assert_eq!(pool.index_of('d'), Some(0));
+        assert_eq!(pool.index_of('a'), None);
+        assert_eq!(pool.char_at(3), None);
+    }
+
+    #[test]
+    fn pool_indices_after_shift_remove() {
+        let mut pool: Pool = "abcd".parse().unwrap();
+        pool.shift_remove(&'a');
+
+        assert_eq!(pool.to_string(), "bcd");
+        assert_eq!(pool.index_of('d'), Some(2));
+        assert_eq!(pool[0], 'b');
+    }
+
+    #[test]
+    fn pool_hash_ignores_order() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        let hash = |pool: &Pool| {
+            let mut hasher = DefaultHasher::new();
+            pool.hash(&mut hasher);
+            hasher.finish()
+        };
+        let pool: Pool = "abc".parse().unwrap();
+        let reversed: Pool = "cba".parse().unwrap();
+
+        assert_eq!(pool, reversed);
+        assert_eq!(hash(&pool), hash(&reversed));
+        assert_ne!(hash(&pool), hash(&"abd".parse().unwrap()));
+
+        let mut cache = HashMap::new();
+        cache.insert(pool, 1);
+
+        assert_eq!(cache.get(&reversed), Some(&1));
+    }
+
+    #[test]
+    fn pool_eq_ordered_after_sort_and_shuffle() {
+        let pool = Pool::alphanumeric();
+        let mut sorted = pool.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+        let mut shuffled = pool.clone();
+        shuffled.shuffle(&mut StdRng::seed_from_u64(42));
+
+        assert_eq!(sorted, pool);
+        assert_eq!(shuffled, pool);
+        assert!(pool.eq_ordered(&pool.clone()));
+        assert!(!sorted.eq_ordered(&pool));
+        assert!(!shuffled.eq_ordered(&pool));
+        assert!(!pool.eq_ordered(&"abc".parse().unwrap()));
+        assert!(Pool::new().eq_ordered(&Pool::new()));
+    }
+
+    #[test]
+    fn pool_shuffle_keeps_chars() {
+        let mut pool = Pool::alphanumeric();
+        pool.shuffle(&mut StdRng::seed_from_u64(42));
+
+        assert_eq!(pool.len(), 62);
+        assert_eq!(pool, Pool::alphanumeric());
+        assert_ne!(pool.to_string(), Pool::alphanumeric().to_string());
+    }
+
+    #[test]
+    fn pool_shuffle_same_seed() {
+        let mut pool = Pool::alphanumeric();
+        let mut other = Pool::alphanumeric();
+        pool.shuffle(&mut StdRng::seed_from_u64(7));
+        other.shuffle(&mut StdRng::seed_from_u64(7));
+
+        assert_eq!(pool.to_string(), other.to_string());
+    }
+
+    #[test]
+    fn pool_shuffled_known_permutation() {
+        let pool = Pool::digits();
+        let shuffled = pool.shuffled(&mut ChaCha20Rng::seed_from_u64(42));
+
+        assert_eq!(shuffled.to_string(), "9347062815");
+        assert_eq!(shuffled.len(), 10);
+        assert!(pool.iter().all(|ch| shuffled.contains(*ch)));
+        assert_eq!(pool.to_string(), DIGITS);
+    }
+
+    #[test]
+    fn pool_sample_known_subset() {
+        let pool = Pool::lowercase();
+        let sample = pool.sample(5, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+
+        assert_eq!(sample.to_string(), "nlewf");
+        assert!(sample.is_subset(&pool));
+    }
+
+    #[test]
+    fn pool_sample_distinct_chars() {
+        let pool = Pool::full();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for n in 0..=pool.len() {
+            let sample = pool.sample(n, &mut rng).unwrap();
+
+            assert_eq!(sample.len(), n);
+            assert!(sample.is_subset(&pool));
+        }
+    }
+
+    #[test]
+    fn pool_sample_too_many() {
+        let result = Pool::digits().sample(11, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(
+            result,
+            Err(PassgenError::PoolTooSmall {
+                len: 10,
+                required: 11
+            })
+        );
+    }
+
+    #[test]
+    fn generate_distinct_password_permutations() {
+        let pool: Pool = "abcdef".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut seen = std::collections::HashMap::new();
+
+        for _ in 0..7200 {
+            let password = generate_distinct_password(&pool, 6, &mut rng).unwrap();
+            let mut chars: Vec<char> = password.chars().collect();
+            chars.sort_unstable();
+
+            assert_eq!(chars, pool.to_vec());
+            *seen.entry(password).or_insert(0) += 1;
+        }
+        assert_eq!(seen.len(), 720);
+        assert!(seen.values().all(|count| (1..=30).contains(count)));
+    }
+
+    #[test]
+    fn generate_distinct_password_errors() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(
+            generate_distinct_password(&Pool::digits(), 11, &mut rng),
+            Err(PassgenError::PoolTooSmall {
+                len: 10,
+                required: 11
+            })
+        );
+        assert_eq!(
+            generate_distinct_password(&Pool::new(), 1, &mut rng),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generate_distinct_password(&Pool::digits(), 0, &mut rng),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+
+    #[test]
+    fn pool_shuffle_empty_and_single() {
+        let mut empty = Pool::new();
+        let mut single: Pool = "a".parse().unwrap();
+        empty.shuffle(&mut StdRng::seed_from_u64(7));
+        single.shuffle(&mut StdRng::seed_from_u64(7));
+
+        assert!(empty.is_empty());
+        assert_eq!(single.to_string(), "a");
+    }
+
+    #[test]
+    fn pool_sort_by_class() {
+        let mut pool = Pool::full();
+        pool.shuffle(&mut StdRng::seed_from_u64(42));
+        pool.sort_by_key(|ch| (!ch.is_ascii_alphabetic(), !ch.is_ascii_digit(), *ch));
+
+        let sorted = pool.to_string();
+
+        assert_eq!(sorted[..52], [UPPERCASE, LOWERCASE].concat());
+        assert_eq!(&sorted[52..62], DIGITS);
+        assert!(sorted[62..].chars().all(|ch| ch.is_ascii_punctuation()));
+        assert!((0..pool.len()).all(|idx| pool.index_of(pool[idx]) == Some(idx)));
+    }
+
+    #[test]
+    fn pool_sort_by_reverse() {
+        let mut pool: Pool = "bca".parse().unwrap();
+        pool.sort_by(|a, b| b.cmp(a));
+
+        assert_eq!(pool.to_string(), "cba");
+        assert_eq!(pool.char_at(0), Some('c'));
+    }
+
+    #[test]
+    fn pool_sort_by_canonical_order() {
+        let order = "qwertyuiopasdfghjklzxcvbnm";
+        let mut pool = Pool::lowercase();
+        pool.sort_by(|a, b| order.find(*a).cmp(&order.find(*b)));
+
+        assert_eq!(pool.to_string(), order);
+    }
+
+    #[test]
+    fn pool_sort_unstable() {
+        let mut pool = Pool::alphanumeric();
+        pool.sort_unstable();
+
+        assert_eq!(pool.to_string(), [DIGITS, UPPERCASE, LOWERCASE].concat());
+    }
+
+    #[test]
+    fn pool_remove_ambiguous() {
+        let mut pool = Pool::alphanumeric();
+        pool.remove_ambiguous();
+
+        assert_eq!(pool.len(), 50);
+        assert!(AMBIGUOUS_CHARS.chars().all(|ch| !pool.contains(ch)));
+    }
+
+    #[test]
+    fn pool_unambiguous_alphanumeric() {
+        let pool = Pool::unambiguous_alphanumeric();
+
+        assert_eq!(pool.len(), 50);
+        assert_eq!(
+            pool.to_string(),
+            "abcdefghijkmnpqrstuvwxyzACDEFGHJKLMNPQRTUVWXY34679"
+        );
+        assert!("0Oo1Il2Z5S8B".chars().all(|ch| !pool.contains(ch)));
+    }
+
+    #[test]
+    fn pool_remove_ambiguous_none_present() {
+        let mut pool: Pool = "acdXY#".parse().unwrap();
+        pool.remove_ambiguous();
+
+        assert_eq!(pool.to_string(), "acdXY#");
+    }
+
+    #[test]
+    fn pool_remove_ambiguous_all_present() {
+        let mut pool: Pool = AMBIGUOUS_CHARS.parse().unwrap();
+
+        assert!(pool.remove_ambiguous().is_empty());
+    }
+
+    #[test]
+    fn pool_remove_ambiguous_keeps_order() {
+        let mut pool: Pool = "x0y1z".parse().unwrap();
+        pool.remove_ambiguous();
+
+        assert_eq!(pool.to_string(), "xyz");
+    }
+
+    #[test]
+    fn pool_without_ambiguous() {
+        let pool: Pool = "|ab|".parse().unwrap();
+
+        assert_eq!(pool.without_ambiguous().to_string(), "ab");
+        assert!(pool.contains('|'));
+    }
+
+    #[test]
+    fn pool_union() {
+        let pool: Pool = "abc".parse().unwrap();
+        let other: Pool = "dcbe".parse().unwrap();
+
+        assert_eq!(pool.union(&other).to_string(), "abcde");
+    }
+
+    #[test]
+    fn pool_union_with_empty() {
+        let pool: Pool = "abc".parse().unwrap();
+
+        assert_eq!(pool.union(&Pool::new()), pool);
+        assert_eq!(Pool::new().union(&pool), pool);
+    }
+
+    #[test]
+    fn pool_intersection() {
+        let pool: Pool = "abcde".parse().unwrap();
+        let other: Pool = "exa".parse().unwrap();
+
+        assert_eq!(pool.intersection(&other).to_string(), "ae");
+    }
+
+    #[test]
+    fn pool_intersection_disjoint() {
+        let pool: Pool = "abc".parse().unwrap();
+        let other: Pool = "xyz".parse().unwrap();
+
+        assert!(pool.intersection(&other).is_empty());
+    }
+
+    #[test]
+    fn pool_difference() {
+        let pool = Pool::alphanumeric();
+        let ambiguous: Pool = "0O1lI".parse().unwrap();
+        let result = pool.difference(&ambiguous);
+
+        assert_eq!(result.len(), 57);
+        assert!(!result.contains('0'));
+        assert!(!result.contains('l'));
+        assert!(result.to_string().starts_with("abcdefghijkmn"));
+    }
+
+    #[test]
+    fn pool_bitor() {
+        let pool: Pool = "ab".parse().unwrap();
+        let other: Pool = "bc".parse().unwrap();
+
+        assert_eq!(&pool | &other, pool.union(&other));
+    }
+
+    #[test]
+    fn pool_bitand() {
+        let pool: Pool = "ab".parse().unwrap();
+        let other: Pool = "bc".parse().unwrap();
+
+        assert_eq!(&pool & &other, "b".parse().unwrap());
+    }
+
+    #[test]
+    fn pool_sub() {
+        let pool: Pool = "ab".parse().unwrap();
+        let other: Pool = "bc".parse().unwrap();
+
+        assert_eq!(&pool - &other, "a".parse().unwrap());
+    }
+
+    #[test]
+    fn pool_symmetric_difference_order() {
+        let pool: Pool = "dcba".parse().unwrap();
+        let other: Pool = "zbxd".parse().unwrap();
+
+        assert_eq!(pool.symmetric_difference(&other).to_string(), "cazx");
+        assert_eq!(other.symmetric_difference(&pool).to_string(), "zxca");
+        assert_eq!((&pool ^ &other).to_string(), "cazx");
+    }
+
+    #[test]
+    fn pool_set_operations_keep_left_order() {
+        let pool: Pool = "zyx123".parse().unwrap();
+        let other: Pool = "321abc".parse().unwrap();
+
+        assert_eq!(pool.union(&other).to_string(), "zyx123abc");
+        assert_eq!(pool.intersection(&other).to_string(), "123");
+        assert_eq!(other.intersection(&pool).to_string(), "321");
+        assert_eq!(pool.difference(&other).to_string(), "zyx");
+    }
+
+    #[test]
+    fn pool_is_subset_is_superset() {
+        let pool: Pool = "cab".parse().unwrap();
+
+        assert!(pool.is_subset(&Pool::lowercase()));
+        assert!(!Pool::lowercase().is_subset(&pool));
+        assert!(Pool::lowercase().is_superset(&pool));
+        assert!(pool.is_subset(&pool) && pool.is_superset(&pool));
+        assert!(Pool::new().is_subset(&pool));
+    }
+
+    #[test]
+    fn generate_password_assert_len() {
+        let pool = "0123456789".chars().collect::<IndexSet<char>>();
+        let password = generate_password(&Pool(pool), 15);
+
+        assert_eq!(password.chars().count(), 15);
+    }
+
+    #[test]
+    fn generate_password_with_rng_assert_len() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let password = generate_password_with_rng(&pool, 15, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(password.chars().count(), 15);
+    }
+
+    #[test]
+    fn generate_password_with_rng_same_seed() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let first = generate_password_with_rng(&pool, 32, &mut StdRng::seed_from_u64(42));
+        let second = generate_password_with_rng(&pool, 32, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generate_password_with_rng_different_seed() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let first = generate_password_with_rng(&pool, 32, &mut StdRng::seed_from_u64(42));
+        let second = generate_password_with_rng(&pool, 32, &mut StdRng::seed_from_u64(43));
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_with_rng_passed_empty_pool() {
+        generate_password_with_rng(&Pool::new(), 15, &mut StdRng::seed_from_u64(42));
+    }
+
+    #[test]
+    fn generate_password_secure_with_os_rng() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let password = generate_password_secure(&pool, 15, &mut OsRng);
+
+        assert_eq!(password.chars().count(), 15);
+    }
+
+    #[test]
+    fn generate_password_secure_with_chacha20_rng() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let first = generate_password_secure(&pool, 15, &mut ChaCha20Rng::seed_from_u64(42));
+        let second = generate_password_secure(&pool, 15, &mut ChaCha20Rng::seed_from_u64(42));
+
+        assert_eq!(first.chars().count(), 15);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generate_password_secure_with_thread_rng() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let password = generate_password_secure(&pool, 15, &mut rand::thread_rng());
+
+        assert_eq!(password.chars().count(), 15);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_secure_passed_empty_pool() {
+        generate_password_secure(&Pool::new(), 15, &mut OsRng);
+    }
+
+    #[test]
+    fn generate_password_os_assert_len() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let password = generate_password_os(&pool, 15);
+
+        assert_eq!(password.chars().count(), 15);
+    }
+
+    #[test]
+    fn generate_n_passwords_with_rng_assert_count() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let vec_passwords =
+            generate_n_passwords_with_rng(&pool, 15, 100, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(vec_passwords.len(), 100);
+        assert!(vec_passwords.iter().all(|p| p.chars().count() == 15));
+    }
+
+    #[test]
+    fn generate_n_passwords_with_rng_same_seed() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let first = generate_n_passwords_with_rng(&pool, 15, 10, &mut StdRng::seed_from_u64(7));
+        let second = generate_n_passwords_with_rng(&pool, 15, 10, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generate_n_passwords_with_rng_matches_single() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let expected = vec![
+            generate_password_with_rng(&pool, 15, &mut rng),
+            generate_password_with_rng(&pool, 15, &mut rng),
+        ];
+
+        assert_eq!(
+            generate_n_passwords_with_rng(&pool, 15, 2, &mut StdRng::seed_from_u64(7)),
+            expected
+        );
+    }
+
+    #[test]
+    fn generate_password_bytes_valid_utf8() {
+        let pool: Pool = "aé€😀".parse().unwrap();
+        let bytes = generate_password_bytes(&pool, 100);
+        let password = std::str::from_utf8(&bytes).unwrap();
+
+        assert_eq!(password.chars().count(), 100);
+        assert!(password.chars().all(|ch| pool.contains(ch)));
+        assert_eq!(generate_password_bytes(&Pool::digits(), 12).len(), 12);
+    }
+
+    #[test]
+    fn generate_password_bytes_passed_zero() {
+        assert!(generate_password_bytes(&Pool::digits(), 0).is_empty());
+    }
+
+    #[test]
+    fn generate_password_dyn_matches_generic() {
+        let pool = Pool::alphanumeric();
+        let mut boxed: Box<dyn RngCore> = Box::new(StdRng::seed_from_u64(42));
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..3 {
+            assert_eq!(
+                generate_password_dyn(&pool, 24, boxed.as_mut()),
+                generate_password_with_rng(&pool, 24, &mut rng)
+            );
+        }
+    }
+
+    #[test]
+    fn generate_password_with_rng_mock_rng() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let password = generate_password_with_rng(&pool, 4, &mut StepRng::new(0, 0));
+
+        assert_eq!(password, "0000");
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_n_passwords_with_rng_passed_empty_pool() {
+        generate_n_passwords_with_rng(&Pool::new(), 15, 10, &mut StdRng::seed_from_u64(42));
+    }
+
+    #[test]
+    fn generate_n_passwords_assert_count() {
+        let pool = "0123456789".chars().collect::<IndexSet<char>>();
+        let vec_passwords = generate_n_passwords(&Pool(pool), 15, 100);
+
+        assert_eq!(vec_passwords.len(), 100);
+    }
+
+    #[test]
+    fn generate_n_passwords_assert_len() {
+        let pool = "0123456789".chars().collect::<IndexSet<char>>();
+        let vec_passwords = generate_n_passwords(&Pool(pool), 15, 100);
+
+        assert_eq!(vec_passwords[3].len(), 15);
     }
 
-    /// Insert char to pool.
-    /// If an equivalent char already exists in the pool, then the pool is not changed.
-    #[allow(dead_code)]
-    pub(crate) fn insert(&mut self, ch: char) {
-        self.0.insert(ch);
+    #[test]
+    fn pool_is_ascii() {
+        let pool = Pool::alphanumeric().union(&Pool::ascii_symbols());
+
+        assert!(pool.is_ascii());
     }
 
-    /// Returns iterator
-    pub fn iter(&self) -> Iter<'_, char> {
-        self.0.iter()
+    #[test]
+    fn pool_is_ascii_assert_false() {
+        let pool: Pool = "abcé".parse().unwrap();
+
+        assert!(!pool.is_ascii());
     }
 
-    /// Remove char from pool. Like a [Vec::swap_remove]
-    pub fn swap_remove(&mut self, ch: &char) -> bool {
-        self.0.swap_remove(ch)
+    #[test]
+    fn pool_is_ascii_empty() {
+        assert!(Pool::new().is_ascii());
     }
 
-    /// Remove char from pool. Like a [Vec::remove]
-    pub fn shift_remove(&mut self, ch: &char) -> bool {
-        self.0.shift_remove(ch)
+    #[test]
+    fn generate_ascii_password_assert_len() {
+        let pool = Pool::alphanumeric().union(&Pool::ascii_symbols());
+        let password = generate_ascii_password(&pool, 72).unwrap();
+
+        assert_eq!(password.len(), 72);
+        assert!(password.chars().all(|ch| pool.contains(ch)));
     }
 
-    /// Remove all chars of the string `elements` from pool
-    pub fn remove_all(&mut self, elements: &str) {
-        elements.chars().for_each(|ch| {
-            self.swap_remove(&ch);
-        });
+    #[test]
+    fn generate_ascii_password_passed_non_ascii_pool() {
+        let pool: Pool = "abcé".parse().unwrap();
+
+        assert_eq!(
+            generate_ascii_password(&pool, 15),
+            Err(PassgenError::NonAsciiPool)
+        );
     }
 
-    /// Sorts the chars in the pool
-    ///
-    /// # Examples
-    /// ```
-    /// # use libpassgen::Pool;
-    /// # use std::str::FromStr;
-    /// let mut pool = Pool::from_str("31524").unwrap();
-    /// pool.sort();
-    ///
-    /// assert_eq!(pool, Pool::from_str("12345").unwrap())
-    /// ```
-    pub fn sort(&mut self) {
-        self.0.sort()
+    #[test]
+    fn generate_ascii_password_passed_empty_pool() {
+        assert_eq!(
+            generate_ascii_password(&Pool::new(), 15),
+            Err(PassgenError::EmptyPool)
+        );
     }
-}
 
-/// Generate random password.
-///
-/// # Examples
-/// ```
-/// # use libpassgen::{Pool, generate_password};
-/// let pool = "0123456789".parse().unwrap();
-/// let password = generate_password(&pool, 15);
-///
-/// assert_eq!(password.chars().count(), 15);
-/// ```
-///
-/// # Panics
-/// Panics if `pool` is empty.
-pub fn generate_password(pool: &Pool, length: usize) -> String {
-    assert!(!pool.is_empty(), "Pool contains no elements!");
+    #[test]
+    fn generate_password_into_assert_len() {
+        let mut password = String::from("previous contents");
+        generate_password_into(&Pool::digits(), 15, &mut password);
 
-    let mut rng = rand::thread_rng();
+        assert_eq!(password.len(), 15);
+        assert!(password.chars().all(|ch| ch.is_ascii_digit()));
+    }
 
-    (0..length)
-        .map(|_| {
-            let idx = rng.gen_range(0.. pool.len());
-            *pool.get(idx).unwrap()
-        })
-        .collect()
-}
+    #[test]
+    fn generate_password_into_reuses_buffer() {
+        let mut password = String::with_capacity(64);
+        let capacity = password.capacity();
+        let ptr = password.as_ptr();
 
-/// Generate multiple random passwords.
-///
-/// # Examples
-/// ```
-/// # use libpassgen::{Pool, generate_n_passwords};
-/// let pool = "0123456789".parse().unwrap();
-/// let vec_passwords = generate_n_passwords(&pool, 15,5);
-///
-/// assert!(!vec_passwords[2].is_empty());
-/// ```
-///
-/// # Panics
-/// Panics if `pool` is empty.
-pub fn generate_n_passwords(pool: &Pool, length: usize, count: usize) -> Vec<String> {
-    assert!(!pool.is_empty(), "Pool contains no elements!");
+        for _ in 0..100 {
+            generate_password_into(&Pool::alphanumeric(), 64, &mut password);
+        }
 
-    let mut vec: Vec<String> = Vec::new();
-    for n in 0..count {
-        let pass = generate_password(pool, length);
-        vec.insert(n, pass.clone());
+        assert_eq!(password.capacity(), capacity);
+        assert_eq!(password.as_ptr(), ptr);
     }
-    vec
-}
 
-/// Calculates entropy.
-///
-/// # Examples
-/// ```
-/// # use libpassgen::calculate_entropy;
-///
-/// assert_eq!(calculate_entropy(12, 64), 72_f64);
-/// ```
-pub fn calculate_entropy(length: usize, pool_size: usize) -> f64 {
-    length as f64 * (pool_size as f64).log2()
-}
+    #[test]
+    fn generate_password_into_grows_buffer() {
+        let mut password = String::new();
+        generate_password_into(&"é".parse().unwrap(), 10, &mut password);
 
-/// Calculates the minimum password length required to obtain a given entropy.
-///
-/// # Examples
-/// ```
-/// # use libpassgen::calculate_length;
-///
-/// assert_eq!(calculate_length(128_f64, 64_f64), 22_f64);
-/// ```
-pub fn calculate_length(entropy: f64, pool_size: f64) -> f64 {
-    (entropy / pool_size.log2()).ceil()
-}
+        assert_eq!(password, "é".repeat(10));
+        assert!(password.capacity() >= 20);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_into_passed_empty_pool() {
+        generate_password_into(&Pool::new(), 15, &mut String::new());
+    }
 
     #[test]
-    fn pool_deref_mut() {
-        let mut pool = Pool::from_str("12345").unwrap();
-        *pool = "abcde".chars().collect::<IndexSet<char>>();
+    fn write_password_assert_len() {
+        let mut out = String::from("> ");
+        write_password(&Pool::digits(), 15, &mut out).unwrap();
 
-        assert_eq!(*pool, "abcde".chars().collect::<IndexSet<char>>())
+        assert_eq!(out.len(), 17);
+        assert!(out.starts_with("> "));
     }
 
     #[test]
-    fn pool_deref() {
-        let pool = Pool::from_str("12345").unwrap();
-
-        assert_eq!(*pool, "12345".chars().collect::<IndexSet<char>>())
+    fn write_password_propagates_error() {
+        struct Failing;
+
+        impl fmt::Write for Failing {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        assert_eq!(
+            write_password(&Pool::digits(), 15, &mut Failing),
+            Err(fmt::Error)
+        );
     }
 
     #[test]
-    fn pool_sort() {
-        let mut pool = Pool::from_str("31524").unwrap();
-        pool.sort();
+    fn write_password_passed_zero_length() {
+        let mut line = String::from("password: ");
+        write_password(&Pool::digits(), 0, &mut line).unwrap();
 
-        assert_eq!(pool, Pool::from_str("12345").unwrap())
+        assert_eq!(line, "password: ");
     }
 
     #[test]
-    fn pool_extend() {
-        let mut pool = Pool::from_str("abc").unwrap();
-        pool.extend(vec!['d', 'e']);
+    fn generate_password_into_passed_zero_length() {
+        let mut password = String::from("previous");
+        generate_password_into(&Pool::digits(), 0, &mut password);
 
-        assert_eq!(pool, Pool::from_str("abcde").unwrap())
+        assert!(password.is_empty());
     }
 
     #[test]
-    fn pool_from_iter() {
-        let iter = vec!['a', 'b', 'c'].into_iter();
+    fn password_iter_take() {
+        let vec_passwords: Vec<String> = password_iter(&Pool::digits(), 15).take(10).collect();
 
-        assert_eq!(iter.collect::<Pool>(), Pool::from_str("abc").unwrap());
+        assert_eq!(vec_passwords.len(), 10);
+        assert!(vec_passwords.iter().all(|p| p.len() == 15));
     }
 
     #[test]
-    fn pool_remove_all() {
-        let mut pool: Pool = "abcde".parse().unwrap();
-        pool.remove_all("ace");
+    fn password_iter_is_infinite() {
+        let pool: Pool = "a".parse().unwrap();
+        let mut iter = password_iter(&pool, 1);
 
-        assert_eq!(pool, "bd".parse::<Pool>().unwrap());
+        assert_eq!(iter.by_ref().take(10_000).count(), 10_000);
+        assert_eq!(iter.next(), Some("a".to_owned()));
     }
 
     #[test]
-    fn pool_swap_remove() {
-        let mut pool: Pool = "abcdefz".parse().unwrap();
+    fn password_iter_find() {
+        let pool: Pool = "ab".parse().unwrap();
+        let password = password_iter(&pool, 4).find(|p| p == "abba");
 
-        assert!(pool.swap_remove(&'b'));
-        assert_eq!(pool.get(1), Some(&'z'));
-        assert_eq!(pool.get(6), None);
+        assert_eq!(password, Some("abba".to_owned()));
     }
 
     #[test]
-    fn pool_shift_remove() {
-        let mut pool: Pool = "abcdefz".parse().unwrap();
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn password_iter_passed_empty_pool() {
+        let pool = Pool::new();
 
-        assert!(pool.shift_remove(&'b'));
-        assert_eq!(pool.get(1), Some(&'c'));
-        assert_eq!(pool.get(6), None);
+        let _ = password_iter(&pool, 4);
     }
 
     #[test]
-    fn pool_iter() {
-        let pool: Pool = "abcdefz".parse().unwrap();
-        let mut iter = pool.iter();
+    fn generate_n_passwords_large_batch() {
+        let vec_passwords = generate_n_passwords(&Pool::alphanumeric(), 64, 100_000);
 
-        assert_eq!(iter.next(), Some(&'a'));
-        assert_eq!(iter.next(), Some(&'b'));
-        assert_eq!(iter.last(), Some(&'z'));
+        assert_eq!(vec_passwords.len(), 100_000);
+        assert!(vec_passwords.iter().all(|p| p.len() == 64));
     }
 
     #[test]
-    fn pool_display() {
-        let pool: Pool = "0123456789".parse().unwrap();
+    fn generate_n_passwords_with_rng_preallocates() {
+        let pool: Pool = "éà€".parse().unwrap();
+        let vec_passwords =
+            generate_n_passwords_with_rng(&pool, 10, 5, &mut StdRng::seed_from_u64(1));
 
-        assert_eq!(pool.to_string(), "0123456789".to_owned());
+        assert_eq!(vec_passwords.capacity(), 5);
+        assert!(vec_passwords.iter().all(|p| p.capacity() == 30));
     }
 
     #[test]
-    fn pool_contains_all() {
-        let pool: Pool = "0123456789".parse().unwrap();
+    fn generate_password_multibyte_pool() {
+        let pool: Pool = "é€😀".parse().unwrap();
+        let password = generate_password(&pool, 20);
 
-        assert!(pool.contains_all("2357"));
+        assert_eq!(password.chars().count(), 20);
+        assert!(password.capacity() <= 80);
     }
 
     #[test]
-    fn pool_contains_all_assert_false() {
-        let pool: Pool = "0123456789".parse().unwrap();
-
-        assert!(!pool.contains_all("0123F"));
+    fn max_char_len_assert_true() {
+        assert_eq!(max_char_len(&Pool::new()), 0);
+        assert_eq!(max_char_len(&Pool::digits()), 1);
+        assert_eq!(max_char_len(&"aé€😀".parse().unwrap()), 4);
     }
 
     #[test]
-    fn pool_contains() {
-        let pool: Pool = "0123456789".parse().unwrap();
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_passed_empty_pool() {
+        let pool = "".chars().collect::<IndexSet<char>>();
 
-        assert!(pool.contains('5'));
+        generate_password(&Pool(pool), 15);
     }
 
     #[test]
-    fn pool_contains_assert_false() {
+    fn generate_password_passed_zero_length() {
         let pool: Pool = "0123456789".parse().unwrap();
 
-        assert!(!pool.contains('A'));
+        assert_eq!(generate_password(&pool, 0), "");
+        assert_eq!(generate_n_passwords(&pool, 0, 3), vec![""; 3]);
     }
 
     #[test]
-    fn pool_get() {
-        let pool: Pool = "ABCD".parse().unwrap();
+    fn generate_password_with_rng_passed_zero_length() {
+        let pool = Pool::digits();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(generate_password_with_rng(&pool, 0, &mut rng), "");
+        assert_eq!(generate_password_dyn(&pool, 0, &mut rng), "");
+        assert_eq!(generate_password_secure(&pool, 0, &mut rng), "");
+        assert_eq!(
+            generate_n_passwords_with_rng(&pool, 0, 3, &mut rng),
+            vec![""; 3]
+        );
+        assert_eq!(
+            password_iter(&pool, 0).take(2).collect::<Vec<_>>(),
+            ["", ""]
+        );
+    }
 
-        assert_eq!(pool.get(0), Some(&'A'))
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_with_rng_passed_empty_pool_and_zero_length() {
+        generate_password_with_rng(&Pool::new(), 0, &mut StdRng::seed_from_u64(42));
     }
 
     #[test]
-    fn pool_is_empty() {
-        let pool = Pool::new();
+    fn try_generate_password_assert_len() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let password = try_generate_password(&pool, 15).unwrap();
 
-        assert!(pool.is_empty());
+        assert_eq!(password.chars().count(), 15);
     }
 
     #[test]
-    fn pool_is_empty_assert_false() {
-        let pool = Pool::from_str("0123456789").unwrap();
+    fn try_generate_password_passed_empty_pool() {
+        let result = try_generate_password(&Pool::new(), 15);
 
-        assert!(!pool.is_empty());
+        assert_eq!(result, Err(PassgenError::EmptyPool));
     }
 
     #[test]
-    fn pool_len() {
+    fn try_generate_password_passed_zero_length() {
         let pool: Pool = "0123456789".parse().unwrap();
+        let result = try_generate_password(&pool, 0);
 
-        assert_eq!(pool.len(), 10)
+        assert_eq!(result, Err(PassgenError::ZeroLength));
     }
 
     #[test]
-    fn pool_insert() {
-        let mut pool = "ABC".parse::<Pool>().unwrap();
-        pool.insert('D');
+    fn generate_password_range_lengths() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = [0; 4];
+
+        for _ in 0..4000 {
+            let password = try_generate_password_range_with_rng(&pool, 5, 8, &mut rng).unwrap();
+            assert!(password.chars().all(|ch| pool.contains(ch)));
+            counts[password.len() - 5] += 1;
+        }
+
+        assert!(counts.iter().all(|count| (900..1100).contains(count)));
+        assert_eq!(generate_password_range(&pool, 7, 7).len(), 7);
+        assert_eq!(generate_password_range(&pool, 0, 0), "");
+        assert!(generate_password_range(&pool, 0, 3).len() <= 3);
+    }
 
-        assert_eq!(pool, "ABCD".parse::<Pool>().unwrap())
+    #[test]
+    #[should_panic(expected = "Minimum length 9 is greater than the maximum length 8!")]
+    fn generate_password_range_inverted() {
+        generate_password_range(&Pool::digits(), 9, 8);
     }
 
     #[test]
-    fn pool_extend_from_string() {
-        let mut pool = "ABC".parse::<Pool>().unwrap();
-        let mut other_pool = pool.clone();
+    fn try_generate_password_range_errors() {
+        assert_eq!(
+            try_generate_password_range(&Pool::digits(), 3, 2),
+            Err(PassgenError::InvalidLengthRange { min: 3, max: 2 })
+        );
+        assert_eq!(
+            try_generate_password_range(&Pool::new(), 2, 3),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            try_generate_password_range(&Pool::digits(), 0, 3),
+            Err(PassgenError::ZeroLength)
+        );
+    }
 
-        other_pool.insert('D');
-        pool.extend_from_string("D");
+    #[test]
+    fn try_generate_n_passwords_assert_count() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let vec_passwords = try_generate_n_passwords(&pool, 15, 10).unwrap();
 
-        assert_eq!(other_pool, pool)
+        assert_eq!(vec_passwords.len(), 10);
     }
 
     #[test]
-    fn pool_from_string() {
-        let indexset: IndexSet<_> = "0123456789".chars().collect();
+    fn try_generate_n_passwords_passed_empty_pool() {
+        let result = try_generate_n_passwords(&Pool::new(), 15, 10);
 
-        assert_eq!(Pool(indexset), "0123456789".to_owned().parse().unwrap())
+        assert_eq!(result, Err(PassgenError::EmptyPool));
     }
 
     #[test]
-    fn pool_from_str() {
-        let indexset: IndexSet<_> = "0123456789".chars().collect();
+    fn try_generate_n_passwords_passed_zero_length() {
+        let pool: Pool = "0123456789".parse().unwrap();
+        let result = try_generate_n_passwords(&pool, 0, 10);
 
-        assert_eq!(Pool(indexset), "0123456789".parse().unwrap())
+        assert_eq!(result, Err(PassgenError::ZeroLength));
     }
 
     #[test]
-    fn generate_password_assert_len() {
-        let pool = "0123456789".chars().collect::<IndexSet<char>>();
-        let password = generate_password(&Pool(pool), 15);
+    fn generate_password_with_entropy_uses_pool_size() {
+        let pool: Pool = "abcd".parse().unwrap();
+        let (password, entropy) = generate_password_with_entropy(&pool, 10);
 
-        assert_eq!(password.chars().count(), 15);
+        assert_eq!(password.len(), 10);
+        assert_eq!(entropy, 20_f64);
     }
 
     #[test]
-    fn generate_n_passwords_assert_count() {
-        let pool = "0123456789".chars().collect::<IndexSet<char>>();
-        let vec_passwords = generate_n_passwords(&Pool(pool), 15, 100);
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_with_entropy_passed_empty_pool() {
+        generate_password_with_entropy(&Pool::new(), 10);
+    }
 
-        assert_eq!(vec_passwords.len(), 100);
+    #[test]
+    fn generate_password_with_entropy_passed_zero_length() {
+        assert_eq!(
+            generate_password_with_entropy(&Pool::digits(), 0),
+            (String::new(), 0_f64)
+        );
     }
 
     #[test]
-    fn generate_n_passwords_assert_len() {
-        let pool = "0123456789".chars().collect::<IndexSet<char>>();
-        let vec_passwords = generate_n_passwords(&Pool(pool), 15, 100);
+    fn generate_n_passwords_with_entropy_passed_zero_length() {
+        assert_eq!(
+            generate_n_passwords_with_entropy(&Pool::digits(), 0, 3),
+            vec![(String::new(), 0_f64); 3]
+        );
+    }
 
-        assert_eq!(vec_passwords[3].len(), 15);
+    #[test]
+    fn generate_n_passwords_with_entropy_assert_count() {
+        let passwords = generate_n_passwords_with_entropy(&Pool::alphanumeric(), 8, 5);
+
+        assert_eq!(passwords.len(), 5);
+        assert!(passwords
+            .iter()
+            .all(|(p, e)| p.len() == 8 && *e == calculate_entropy(8, 62)));
     }
 
     #[test]
-    #[should_panic(expected = "Pool contains no elements!")]
-    fn generate_password_passed_empty_pool() {
-        let pool = "".chars().collect::<IndexSet<char>>();
+    fn generate_for_entropy_reaches_entropy() {
+        for (pool, min_entropy, length) in [
+            (Pool::digits(), 20.0, 7),
+            (Pool::alphanumeric(), 128.0, 22),
+            ("ab".parse().unwrap(), 64.0, 64),
+            (Pool::digits(), 0.0, 1),
+            (Pool::digits(), -5.0, 1),
+        ] {
+            let password = generate_for_entropy(&pool, min_entropy);
+
+            assert_eq!(password.len(), length);
+            assert!(calculate_entropy(length, pool.len()) >= min_entropy);
+        }
+    }
 
-        generate_password(&Pool(pool), 15);
+    #[test]
+    fn try_generate_for_entropy_errors() {
+        assert_eq!(
+            try_generate_for_entropy(&"a".parse().unwrap(), 10.0),
+            Err(PassgenError::PoolTooSmall {
+                len: 1,
+                required: 2
+            })
+        );
+        assert_eq!(
+            try_generate_for_entropy(&Pool::new(), 10.0),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            try_generate_for_entropy(&Pool::digits(), f64::INFINITY),
+            Err(PassgenError::InvalidEntropy)
+        );
+        assert_eq!(
+            try_generate_for_entropy(&Pool::digits(), f64::NAN),
+            Err(PassgenError::InvalidEntropy)
+        );
+        assert_eq!(
+            try_generate_for_entropy(&Pool::digits(), 1e300),
+            Err(PassgenError::EntropyTooLarge)
+        );
+        assert_eq!(
+            try_generate_for_entropy(&Pool::digits(), f64::MAX),
+            Err(PassgenError::EntropyTooLarge)
+        );
     }
 
     #[test]
@@ -529,4 +3496,33 @@ mod tests {
 
         assert_eq!(length, f64::INFINITY);
     }
+
+    #[test]
+    fn try_calculate_length_valid() {
+        assert_eq!(try_calculate_length(128_f64, 64_f64), Ok(22_f64));
+        assert_eq!(try_calculate_length(0_f64, 64_f64), Ok(0_f64));
+    }
+
+    #[test]
+    fn try_calculate_length_degenerate_pool() {
+        for pool_size in [1_f64, 0.5, 0_f64, -3_f64, f64::NAN] {
+            assert_eq!(
+                try_calculate_length(0_f64, pool_size),
+                Err(PassgenError::PoolTooSmall {
+                    len: pool_size as usize,
+                    required: 2
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn try_calculate_length_invalid_entropy() {
+        for entropy in [-1_f64, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                try_calculate_length(entropy, 64_f64),
+                Err(PassgenError::InvalidEntropy)
+            );
+        }
+    }
 }
@@ -0,0 +1,149 @@
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Aho-Corasick automaton finding any of a set of words in a text in a single pass over it,
+/// ignoring case
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct SubstringMatcher {
+    words: Vec<String>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Node {
+    next: BTreeMap<char, usize>,
+    /// Node of the longest proper suffix of this node which is also a prefix of a word
+    fail: usize,
+    /// Word ending at this node, or else the longest one ending at a suffix of it
+    output: Option<usize>,
+}
+
+/// Lowercase `s` char by char, as [`SubstringMatcher::find`] does with the text.
+///
+/// Unlike [`str::to_lowercase`], a final `Σ` becomes `σ` and not `ς`, so a word and the same
+/// text in uppercase always match.
+pub(crate) fn lowercase(s: &str) -> String {
+    s.chars().flat_map(char::to_lowercase).collect()
+}
+
+impl SubstringMatcher {
+    /// Build the automaton of `words` lowercased with [`lowercase`], ignoring the empty one
+    pub(crate) fn new(words: &BTreeSet<String>) -> Self {
+        let words: Vec<String> = words.iter().filter(|w| !w.is_empty()).cloned().collect();
+        let mut nodes = vec![Node::default()];
+
+        for (idx, word) in words.iter().enumerate() {
+            let mut state = 0;
+            for ch in word.chars() {
+                state = match nodes[state].next.get(&ch) {
+                    Some(next) => *next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[state].next.insert(ch, next);
+                        next
+                    }
+                };
+            }
+            nodes[state].output = Some(idx);
+        }
+
+        // Children of the root fail to it, deeper nodes are linked breadth first
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let children: Vec<(char, usize)> =
+                nodes[state].next.iter().map(|(ch, n)| (*ch, *n)).collect();
+            for (ch, child) in children {
+                let mut fail = nodes[state].fail;
+                while fail != 0 && !nodes[fail].next.contains_key(&ch) {
+                    fail = nodes[fail].fail;
+                }
+                let fail = nodes[fail].next.get(&ch).copied().unwrap_or(0);
+                nodes[child].fail = fail;
+                if nodes[child].output.is_none() {
+                    nodes[child].output = nodes[fail].output;
+                }
+                queue.push_back(child);
+            }
+        }
+
+        SubstringMatcher { words, nodes }
+    }
+
+    /// Returns the word found first in `text`, the one ending first, ignoring case
+    pub(crate) fn find(&self, text: &str) -> Option<&str> {
+        if self.words.is_empty() {
+            return None;
+        }
+
+        let mut state = 0;
+        for ch in text.chars().flat_map(char::to_lowercase) {
+            while state != 0 && !self.nodes[state].next.contains_key(&ch) {
+                state = self.nodes[state].fail;
+            }
+            state = self.nodes[state].next.get(&ch).copied().unwrap_or(0);
+            if let Some(idx) = self.nodes[state].output {
+                return Some(&self.words[idx]);
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for SubstringMatcher {
+    fn default() -> Self {
+        SubstringMatcher::new(&BTreeSet::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(words: &[&str]) -> SubstringMatcher {
+        SubstringMatcher::new(&words.iter().map(|word| lowercase(word)).collect())
+    }
+
+    #[test]
+    fn find_overlapping_words() {
+        let matcher = matcher(&["he", "she", "his", "hers"]);
+
+        assert_eq!(matcher.find("ushers"), Some("she"));
+        assert_eq!(matcher.find("ahishers"), Some("his"));
+        assert_eq!(matcher.find("xhxsx"), None);
+    }
+
+    #[test]
+    fn find_word_through_failure_link() {
+        let matcher = matcher(&["abcd", "bc"]);
+
+        assert_eq!(matcher.find("xabcx"), Some("bc"));
+        assert_eq!(matcher.find("abd"), None);
+        assert_eq!(matcher.find("aabcd"), Some("bc"));
+    }
+
+    #[test]
+    fn find_ignores_case() {
+        let matcher = matcher(&["God", "PASS"]);
+
+        assert_eq!(matcher.find("x9GOD!"), Some("god"));
+        assert_eq!(matcher.find("PaSsWoRd"), Some("pass"));
+    }
+
+    #[test]
+    fn find_final_sigma() {
+        let matcher = matcher(&["ΘΕΟΣ"]);
+
+        assert_eq!(matcher.find("1ΘΕΟΣ!"), Some("θεοσ"));
+        assert_eq!(matcher.find("θεοσ"), Some("θεοσ"));
+    }
+
+    #[test]
+    fn find_without_words() {
+        assert_eq!(matcher(&[]).find("anything"), None);
+        assert_eq!(matcher(&[""]).find("anything"), None);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::Pool;
+use alloc::vec::Vec;
+use core::iter;
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normalization forms accepted by [`Pool::from_str_normalized`] and
+/// [`Pool::normalize`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Normalization {
+    /// Canonical composition: `e` followed by a combining acute accent becomes `é`
+    Nfc,
+    /// Compatibility composition: like [`Normalization::Nfc`], and compatibility chars are
+    /// replaced by their plain equivalent, so `ﬁ` becomes `f` and `i`
+    Nfkc,
+}
+
+impl Pool {
+    /// Create pool from the chars of `s` once normalized to `form`, so that a char and its
+    /// decomposed form, which usually render the same, give a single entry.
+    ///
+    /// Normalization may merge chars, so the pool can be smaller than with
+    /// [`FromStr`](core::str::FromStr), and passwords drawn from it have less entropy per char.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Normalization, Pool};
+    /// // "é" twice: precomposed, then "e" and a combining acute accent
+    /// let pool = Pool::from_str_normalized("\u{e9}e\u{301}", Normalization::Nfc);
+    ///
+    /// assert_eq!(pool.to_string(), "\u{e9}");
+    /// ```
+    pub fn from_str_normalized(s: &str, form: Normalization) -> Pool {
+        match form {
+            Normalization::Nfc => s.nfc().collect(),
+            Normalization::Nfkc => s.nfkc().collect(),
+        }
+    }
+
+    /// Normalize each char of the pool to `form`, keeping the order of the first occurrences.
+    ///
+    /// Chars are normalized one by one: a combining mark already in the pool stays on its own,
+    /// but chars with the same normalized form are merged, which shrinks the pool and lowers
+    /// the entropy of passwords drawn from it. A char normalized to several chars is replaced
+    /// by all of them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Normalization, Pool};
+    /// // "Å" and the angstrom sign
+    /// let mut pool: Pool = "\u{c5}\u{212b}".parse().unwrap();
+    /// pool.normalize(Normalization::Nfc);
+    ///
+    /// assert_eq!(pool.len(), 1);
+    /// ```
+    pub fn normalize(&mut self, form: Normalization) -> &mut Self {
+        *self = self
+            .iter()
+            .flat_map(|ch| -> Vec<char> {
+                match form {
+                    Normalization::Nfc => iter::once(*ch).nfc().collect(),
+                    Normalization::Nfkc => iter::once(*ch).nfkc().collect(),
+                }
+            })
+            .collect();
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_normalized_merges_combining_mark() {
+        let input = "e\u{301}\u{e9}a";
+        let plain: Pool = input.parse().unwrap();
+        let pool = Pool::from_str_normalized(input, Normalization::Nfc);
+
+        assert_eq!(plain.len(), 4);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.to_string(), "\u{e9}a");
+    }
+
+    #[test]
+    fn from_str_normalized_nfkc() {
+        let nfc = Pool::from_str_normalized("\u{fb01}fi", Normalization::Nfc);
+        let nfkc = Pool::from_str_normalized("\u{fb01}fi", Normalization::Nfkc);
+
+        assert_eq!(nfc.len(), 3);
+        assert_eq!(nfkc.to_string(), "fi");
+    }
+
+    #[test]
+    fn normalize_existing_pool() {
+        let mut pool: Pool = "a\u{212b}\u{c5}\u{fb01}".parse().unwrap();
+
+        pool.normalize(Normalization::Nfc);
+        assert_eq!(pool.to_string(), "a\u{c5}\u{fb01}");
+
+        pool.normalize(Normalization::Nfkc);
+        assert_eq!(pool.to_string(), "a\u{c5}fi");
+    }
+}
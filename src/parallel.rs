@@ -0,0 +1,123 @@
+use crate::{sample_password, unwrap_or_empty, validate, Pool};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+
+/// Generate multiple random passwords on all the threads of the rayon thread pool.
+///
+/// Requires the `parallel` feature, also enabled by its `rayon` alias.
+///
+/// Each worker thread uses its own [`rand::thread_rng`]. The passwords are distributed
+/// exactly as with [`generate_n_passwords`](crate::generate_n_passwords), but the order in
+/// which they were generated is not meaningful.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_n_passwords_parallel};
+/// let pool = "0123456789".parse().unwrap();
+/// let vec_passwords = generate_n_passwords_parallel(&pool, 15, 1000);
+///
+/// assert_eq!(vec_passwords.len(), 1000);
+/// ```
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_n_passwords_parallel(pool: &Pool, length: usize, count: usize) -> Vec<String> {
+    unwrap_or_empty(validate(pool, length));
+
+    (0..count)
+        .into_par_iter()
+        .map_init(rand::thread_rng, |rng, _| {
+            sample_password(pool, length, rng)
+        })
+        .collect()
+}
+
+/// Generate multiple random passwords on all the threads of the rayon thread pool, each
+/// worker using its own [`StdRng`] seeded from the operating system RNG.
+///
+/// Requires the `parallel` feature, also enabled by its `rayon` alias.
+///
+/// The workers never share a generator, so no thread waits on another. The order of the
+/// returned passwords is not deterministic: it depends on how rayon splits the work.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_n_passwords_par};
+/// let vec_passwords = generate_n_passwords_par(&Pool::alphanumeric(), 15, 1000);
+///
+/// assert_eq!(vec_passwords.len(), 1000);
+/// ```
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty or the operating system RNG fails.
+pub fn generate_n_passwords_par(pool: &Pool, length: usize, count: usize) -> Vec<String> {
+    unwrap_or_empty(validate(pool, length));
+
+    (0..count)
+        .into_par_iter()
+        .map_init(StdRng::from_entropy, |rng, _| {
+            sample_password(pool, length, rng)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_n_passwords;
+
+    #[test]
+    fn generate_n_passwords_parallel_matches_sequential() {
+        let pool = Pool::alphanumeric();
+        let parallel = generate_n_passwords_parallel(&pool, 24, 10_000);
+        let sequential = generate_n_passwords(&pool, 24, 10_000);
+
+        assert_eq!(parallel.len(), sequential.len());
+        assert!(parallel.iter().all(|p| p.chars().count() == 24));
+        assert!(parallel
+            .iter()
+            .flat_map(|p| p.chars())
+            .all(|ch| pool.contains(ch)));
+    }
+
+    #[test]
+    fn generate_n_passwords_parallel_zero_count() {
+        assert!(generate_n_passwords_parallel(&Pool::digits(), 8, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_n_passwords_parallel_passed_empty_pool() {
+        generate_n_passwords_parallel(&Pool::new(), 8, 10);
+    }
+
+    #[test]
+    fn generate_n_passwords_par_count() {
+        let pool = Pool::alphanumeric();
+        let passwords = generate_n_passwords_par(&pool, 24, 10_000);
+        let unique: std::collections::HashSet<&String> = passwords.iter().collect();
+
+        assert_eq!(passwords.len(), 10_000);
+        assert_eq!(unique.len(), 10_000);
+        assert!(passwords
+            .iter()
+            .all(|p| p.chars().count() == 24 && p.chars().all(|ch| pool.contains(ch))));
+    }
+
+    #[test]
+    fn generate_n_passwords_par_passed_zero_length() {
+        assert_eq!(
+            generate_n_passwords_par(&Pool::digits(), 0, 10),
+            vec![""; 10]
+        );
+        assert_eq!(
+            generate_n_passwords_parallel(&Pool::digits(), 0, 10),
+            vec![""; 10]
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{sample_password, PassgenError, Pool};
+use alloc::string::String;
+use alloc::vec::Vec;
+use rand::Rng;
+
+/// Generate a passphrase of `count` words picked uniformly at random from `words`, joined
+/// with `separator`. The same word may be picked several times and duplicate entries in
+/// `words` are allowed (they make that word more likely).
+///
+/// # Examples
+/// ```
+/// # use libpassgen::generate_passphrase;
+/// let words = ["correct", "horse", "battery", "staple"];
+/// let passphrase = generate_passphrase(&words, 5, "-");
+///
+/// assert_eq!(passphrase.split('-').count(), 5);
+/// ```
+///
+/// # Panics
+/// Panics if `words` is empty or `count` is zero. See [`try_generate_passphrase`] for a
+/// non-panicking version.
+#[cfg(feature = "std")]
+pub fn generate_passphrase(words: &[&str], count: usize, separator: &str) -> String {
+    try_generate_passphrase(words, count, separator).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Generate a passphrase, returning an error instead of panicking.
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyWordList`] if `words` is empty and
+/// [`PassgenError::ZeroLength`] if `count` is zero.
+#[cfg(feature = "std")]
+pub fn try_generate_passphrase(
+    words: &[&str],
+    count: usize,
+    separator: &str,
+) -> Result<String, PassgenError> {
+    validate_words(words, count)?;
+
+    Ok(sample_passphrase(
+        words,
+        count,
+        separator,
+        &mut rand::thread_rng(),
+    ))
+}
+
+/// Generate a passphrase using the given random number generator.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::generate_passphrase_with_rng;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let words = ["correct", "horse", "battery", "staple"];
+///
+/// assert_eq!(
+///     generate_passphrase_with_rng(&words, 4, " ", &mut StdRng::seed_from_u64(42)),
+///     generate_passphrase_with_rng(&words, 4, " ", &mut StdRng::seed_from_u64(42))
+/// );
+/// # }
+/// ```
+///
+/// # Panics
+/// Panics if `words` is empty or `count` is zero.
+pub fn generate_passphrase_with_rng<R: Rng + ?Sized>(
+    words: &[&str],
+    count: usize,
+    separator: &str,
+    rng: &mut R,
+) -> String {
+    validate_words(words, count).unwrap_or_else(|e| panic!("{}", e));
+
+    sample_passphrase(words, count, separator, rng)
+}
+
+/// Generate a memorable password made of `word_count` capitalized words picked uniformly at
+/// random from `words`, followed by `digit_count` random digits, like `CorrectHorse42`.
+///
+/// The words contribute `word_count * log2(words.len())` bits of entropy and the digits
+/// `digit_count * log2(10)`, about 3.3 bits each. Capitalizing the words is predictable and
+/// adds nothing, and duplicate entries in `words` lower the entropy of the words part.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::generate_word_number;
+/// let words = ["correct", "horse", "battery", "staple"];
+/// let password = generate_word_number(&words, 2, 4);
+///
+/// assert!(password.starts_with(char::is_uppercase));
+/// assert!(password[password.len() - 4..].chars().all(|ch| ch.is_ascii_digit()));
+/// ```
+///
+/// # Panics
+/// Panics if `words` is empty or `word_count` is zero. See [`try_generate_word_number`] for a
+/// non-panicking version.
+#[cfg(feature = "std")]
+pub fn generate_word_number(words: &[&str], word_count: usize, digit_count: usize) -> String {
+    try_generate_word_number(words, word_count, digit_count).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Generate a memorable word and number password, returning an error instead of panicking.
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyWordList`] if `words` is empty and
+/// [`PassgenError::ZeroLength`] if `word_count` is zero.
+#[cfg(feature = "std")]
+pub fn try_generate_word_number(
+    words: &[&str],
+    word_count: usize,
+    digit_count: usize,
+) -> Result<String, PassgenError> {
+    validate_words(words, word_count)?;
+
+    Ok(sample_word_number(
+        words,
+        word_count,
+        digit_count,
+        &mut rand::thread_rng(),
+    ))
+}
+
+/// Generate a memorable word and number password using the given random number generator.
+///
+/// # Panics
+/// Panics if `words` is empty or `word_count` is zero.
+pub fn generate_word_number_with_rng<R: Rng + ?Sized>(
+    words: &[&str],
+    word_count: usize,
+    digit_count: usize,
+    rng: &mut R,
+) -> String {
+    validate_words(words, word_count).unwrap_or_else(|e| panic!("{}", e));
+
+    sample_word_number(words, word_count, digit_count, rng)
+}
+
+fn validate_words(words: &[&str], count: usize) -> Result<(), PassgenError> {
+    if words.is_empty() {
+        return Err(PassgenError::EmptyWordList);
+    }
+    if count == 0 {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    Ok(())
+}
+
+fn sample_passphrase<R: Rng + ?Sized>(
+    words: &[&str],
+    count: usize,
+    separator: &str,
+    rng: &mut R,
+) -> String {
+    (0..count)
+        .map(|_| pick_word(words, rng))
+        .collect::<Vec<&str>>()
+        .join(separator)
+}
+
+fn sample_word_number<R: Rng + ?Sized>(
+    words: &[&str],
+    word_count: usize,
+    digit_count: usize,
+    rng: &mut R,
+) -> String {
+    let mut password = String::new();
+    for _ in 0..word_count {
+        let mut chars = pick_word(words, rng).chars();
+        if let Some(first) = chars.next() {
+            password.extend(first.to_uppercase());
+            password.push_str(chars.as_str());
+        }
+    }
+    password.push_str(&sample_password(&Pool::digits(), digit_count, rng));
+
+    password
+}
+
+fn pick_word<'a, R: Rng + ?Sized>(words: &[&'a str], rng: &mut R) -> &'a str {
+    words[rng.gen_range(0..words.len())]
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const WORDS: [&str; 4] = ["correct", "horse", "battery", "staple"];
+
+    #[test]
+    fn generate_passphrase_assert_count() {
+        let passphrase = generate_passphrase(&WORDS, 6, " ");
+
+        assert_eq!(passphrase.split(' ').count(), 6);
+        assert!(passphrase.split(' ').all(|word| WORDS.contains(&word)));
+    }
+
+    #[test]
+    fn generate_passphrase_empty_separator() {
+        let passphrase = generate_passphrase(&["ab"], 3, "");
+
+        assert_eq!(passphrase, "ababab");
+    }
+
+    #[test]
+    fn generate_passphrase_duplicate_words() {
+        let passphrase = generate_passphrase(&["same", "same"], 3, "-");
+
+        assert_eq!(passphrase, "same-same-same");
+    }
+
+    #[test]
+    #[should_panic(expected = "Word list contains no elements!")]
+    fn generate_passphrase_passed_empty_words() {
+        generate_passphrase(&[], 4, " ");
+    }
+
+    #[test]
+    fn try_generate_passphrase_passed_empty_words() {
+        assert_eq!(
+            try_generate_passphrase(&[], 4, " "),
+            Err(PassgenError::EmptyWordList)
+        );
+    }
+
+    #[test]
+    fn try_generate_passphrase_passed_zero_count() {
+        assert_eq!(
+            try_generate_passphrase(&WORDS, 0, " "),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+
+    #[test]
+    fn generate_passphrase_with_rng_same_seed() {
+        let first = generate_passphrase_with_rng(&WORDS, 8, " ", &mut StdRng::seed_from_u64(1));
+        let second = generate_passphrase_with_rng(&WORDS, 8, " ", &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generate_word_number_format() {
+        let capitalized = ["Correct", "Horse", "Battery", "Staple"];
+
+        for _ in 0..100 {
+            let password = generate_word_number(&WORDS, 2, 3);
+            let (letters, digits) = password.split_at(password.len() - 3);
+
+            assert!(digits.chars().all(|ch| ch.is_ascii_digit()));
+            assert!(capitalized.iter().any(|first| capitalized
+                .iter()
+                .any(|second| letters == [*first, *second].concat())));
+        }
+    }
+
+    #[test]
+    fn generate_word_number_without_digits() {
+        assert_eq!(generate_word_number(&["élan"], 2, 0), "ÉlanÉlan");
+    }
+
+    #[test]
+    fn generate_word_number_with_rng_same_seed() {
+        assert_eq!(
+            generate_word_number_with_rng(&WORDS, 3, 2, &mut StdRng::seed_from_u64(42)),
+            generate_word_number_with_rng(&WORDS, 3, 2, &mut StdRng::seed_from_u64(42))
+        );
+    }
+
+    #[test]
+    fn try_generate_word_number_errors() {
+        assert_eq!(
+            try_generate_word_number(&[], 2, 2),
+            Err(PassgenError::EmptyWordList)
+        );
+        assert_eq!(
+            try_generate_word_number(&WORDS, 0, 2),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    calculate_entropy, password_iter_with_rng, sample_password, unwrap_or_empty, validate, Pool,
+};
+use std::fmt;
+
+/// Generated password which does not show its value in logs.
+///
+/// `Debug` only prints the length and entropy, e.g. `Password(len=16, ~95 bits)`, and there
+/// is no `Display` impl: the value has to be read explicitly with [`Password::reveal`].
+#[derive(Clone, PartialEq)]
+pub struct Password {
+    value: String,
+    entropy: f64,
+}
+
+impl Password {
+    /// Returns the password
+    pub fn reveal(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the number of chars of the password
+    pub fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    /// Returns true if the password has no chars
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Returns the entropy in bits of the password, computed with [`calculate_entropy`] from
+    /// the pool it was generated from
+    pub fn entropy(&self) -> f64 {
+        self.entropy
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Password(len={}, ~{:.0} bits)", self.len(), self.entropy)
+    }
+}
+
+/// Generate random [`Password`], which hides its value from `Debug` output.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_typed};
+/// let password = generate_password_typed(&Pool::alphanumeric(), 16);
+///
+/// assert_eq!(format!("{:?}", password), "Password(len=16, ~95 bits)");
+/// assert_eq!(password.reveal().len(), 16);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_password_typed(pool: &Pool, length: usize) -> Password {
+    unwrap_or_empty(validate(pool, length));
+
+    Password {
+        value: sample_password(pool, length, &mut rand::thread_rng()),
+        entropy: calculate_entropy(length, pool.len()),
+    }
+}
+
+/// Generate multiple random [`Password`]s.
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+pub fn generate_n_passwords_typed(pool: &Pool, length: usize, count: usize) -> Vec<Password> {
+    let entropy = calculate_entropy(length, pool.len());
+
+    password_iter_with_rng(pool, length, rand::thread_rng())
+        .take(count)
+        .map(|value| Password { value, entropy })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_password_typed_reveal() {
+        let password = generate_password_typed(&Pool::digits(), 15);
+
+        assert_eq!(password.reveal().len(), 15);
+        assert_eq!(password.len(), 15);
+        assert!(!password.is_empty());
+    }
+
+    #[test]
+    fn generate_password_typed_entropy() {
+        let password = generate_password_typed(&"abcd".parse().unwrap(), 8);
+
+        assert_eq!(password.entropy(), 16_f64);
+    }
+
+    #[test]
+    fn password_debug_is_redacted() {
+        let pool: Pool = "QZ".parse().unwrap();
+        let password = generate_password_typed(&pool, 16);
+        let debug = format!("{:?}", password);
+
+        assert_eq!(debug, "Password(len=16, ~16 bits)");
+        assert!(!debug.contains('Q') && !debug.contains('Z'));
+    }
+
+    #[test]
+    fn password_debug_in_vec_is_redacted() {
+        let pool: Pool = "QZ".parse().unwrap();
+        let debug = format!("{:?}", generate_n_passwords_typed(&pool, 4, 3));
+
+        assert!(!debug.contains('Q') && !debug.contains('Z'));
+    }
+
+    #[test]
+    fn generate_n_passwords_typed_assert_count() {
+        let passwords = generate_n_passwords_typed(&Pool::alphanumeric(), 16, 10);
+
+        assert_eq!(passwords.len(), 10);
+        assert!(passwords.iter().all(|p| p.len() == 16));
+        assert!(passwords
+            .iter()
+            .all(|p| p.entropy() == calculate_entropy(16, 62)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_password_typed_passed_empty_pool() {
+        generate_password_typed(&Pool::new(), 15);
+    }
+
+    #[test]
+    fn generate_password_typed_zero_length() {
+        let password = generate_password_typed(&Pool::digits(), 0);
+
+        assert_eq!(password.reveal(), "");
+        assert_eq!(password.entropy(), 0_f64);
+    }
+}
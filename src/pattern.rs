@@ -0,0 +1,273 @@
+use crate::{PassgenError, Pool};
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Generate password following `pattern`, where each placeholder is replaced by a random
+/// char of its class and other chars are kept as is.
+///
+/// | Placeholder | Replaced by                               |
+/// |-------------|-------------------------------------------|
+/// | `A`         | uppercase letter, see [`Pool::uppercase`] |
+/// | `a`         | lowercase letter, see [`Pool::lowercase`] |
+/// | `9`         | digit, see [`Pool::digits`]               |
+/// | `L`         | letter of any case                        |
+/// | `X`         | letter or digit, see [`Pool::alphanumeric`] |
+/// | `S`         | symbol, see [`Pool::ascii_symbols`]       |
+///
+/// Every other ASCII letter or digit is reserved and rejected. Any char preceded by a
+/// backslash is kept literally, so `\A` produces `A` and `\\` produces `\`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::generate_from_pattern;
+/// let password = generate_from_pattern("Aaa-999");
+///
+/// assert_eq!(password.len(), 7);
+/// assert_eq!(&password[3..4], "-");
+/// ```
+///
+/// # Panics
+/// Panics if the pattern is empty or invalid. See [`try_generate_from_pattern`] for a
+/// non-panicking version.
+pub fn generate_from_pattern(pattern: &str) -> String {
+    try_generate_from_pattern(pattern).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Generate password following `pattern`, returning an error instead of panicking.
+/// See [`generate_from_pattern`] for the syntax.
+///
+/// # Errors
+/// Returns [`PassgenError::ZeroLength`] if `pattern` is empty,
+/// [`PassgenError::UnknownPlaceholder`] if it contains a reserved char which is not a
+/// placeholder and [`PassgenError::DanglingEscape`] if it ends with an unescaped backslash.
+pub fn try_generate_from_pattern(pattern: &str) -> Result<String, PassgenError> {
+    let tokens = parse_pattern(pattern)?;
+    let mut rng = rand::thread_rng();
+
+    Ok(tokens
+        .iter()
+        .map(|token| match token {
+            Token::Literal(ch) => *ch,
+            Token::Class(pool) => *pool.get(rng.gen_range(0..pool.len())).unwrap(),
+        })
+        .collect())
+}
+
+/// Generate password following `mask`, where each `{name}` placeholder is replaced by a
+/// random char of the pool `name` of `named_pools` and other chars are kept as is. A char
+/// preceded by a backslash is kept literally, so `\{` produces `{`.
+///
+/// Unlike [`generate_from_pattern`], the alphabet of each position is fully custom.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_from_mask};
+/// use std::collections::HashMap;
+///
+/// let mut pools = HashMap::new();
+/// pools.insert(String::from("upper"), Pool::uppercase());
+/// pools.insert(String::from("hex"), "0123456789abcdef".parse().unwrap());
+///
+/// let password = generate_from_mask("{upper}-{hex}{hex}{hex}", &pools).unwrap();
+///
+/// assert_eq!(password.len(), 5);
+/// assert!(password.starts_with(|ch: char| ch.is_ascii_uppercase()));
+/// assert_eq!(&password[1..2], "-");
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::ZeroLength`] if `mask` is empty,
+/// [`PassgenError::UnknownPoolName`] if a placeholder names a pool missing from
+/// `named_pools`, [`PassgenError::EmptyPool`] if it names an empty pool,
+/// [`PassgenError::UnclosedPlaceholder`] if a `{` is never closed and
+/// [`PassgenError::DanglingEscape`] if `mask` ends with an unescaped backslash.
+pub fn generate_from_mask(
+    mask: &str,
+    named_pools: &HashMap<String, Pool>,
+) -> Result<String, PassgenError> {
+    if mask.is_empty() {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    let mut tokens = Vec::new();
+    let mut chars = mask.chars();
+    while let Some(ch) = chars.next() {
+        let token = match ch {
+            '\\' => MaskToken::Literal(chars.next().ok_or(PassgenError::DanglingEscape)?),
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next().ok_or(PassgenError::UnclosedPlaceholder)? {
+                        '}' => break,
+                        ch => name.push(ch),
+                    }
+                }
+                let pool = named_pools
+                    .get(&name)
+                    .ok_or(PassgenError::UnknownPoolName(name))?;
+                if pool.is_empty() {
+                    return Err(PassgenError::EmptyPool);
+                }
+                MaskToken::Pool(pool)
+            }
+            ch => MaskToken::Literal(ch),
+        };
+        tokens.push(token);
+    }
+    let mut rng = rand::thread_rng();
+
+    Ok(tokens
+        .iter()
+        .map(|token| match token {
+            MaskToken::Literal(ch) => *ch,
+            MaskToken::Pool(pool) => pool[rng.gen_range(0..pool.len())],
+        })
+        .collect())
+}
+
+enum MaskToken<'a> {
+    Literal(char),
+    Pool(&'a Pool),
+}
+
+enum Token {
+    Literal(char),
+    Class(Pool),
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Token>, PassgenError> {
+    if pattern.is_empty() {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        let token = match ch {
+            '\\' => Token::Literal(chars.next().ok_or(PassgenError::DanglingEscape)?),
+            'A' => Token::Class(Pool::uppercase()),
+            'a' => Token::Class(Pool::lowercase()),
+            '9' => Token::Class(Pool::digits()),
+            'L' => Token::Class(Pool::lowercase().union(&Pool::uppercase())),
+            'X' => Token::Class(Pool::alphanumeric()),
+            'S' => Token::Class(Pool::ascii_symbols()),
+            ch if ch.is_ascii_alphanumeric() => return Err(PassgenError::UnknownPlaceholder(ch)),
+            ch => Token::Literal(ch),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_from_pattern_classes() {
+        let password: Vec<char> = generate_from_pattern("Aa9LXS").chars().collect();
+
+        assert!(password[0].is_ascii_uppercase());
+        assert!(password[1].is_ascii_lowercase());
+        assert!(password[2].is_ascii_digit());
+        assert!(password[3].is_ascii_alphabetic());
+        assert!(password[4].is_ascii_alphanumeric());
+        assert!(password[5].is_ascii_punctuation());
+    }
+
+    #[test]
+    fn generate_from_pattern_literals() {
+        let password = generate_from_pattern("Aaa-999");
+        let chars: Vec<char> = password.chars().collect();
+
+        assert_eq!(chars.len(), 7);
+        assert_eq!(chars[3], '-');
+        assert!(chars[4..].iter().all(char::is_ascii_digit));
+    }
+
+    #[test]
+    fn generate_from_pattern_escapes() {
+        assert_eq!(generate_from_pattern(r"\A\a\9\\"), r"Aa9\");
+    }
+
+    #[test]
+    fn generate_from_pattern_non_ascii_literal() {
+        assert_eq!(generate_from_pattern("é-€"), "é-€");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown placeholder 'b' in pattern!")]
+    fn generate_from_pattern_unknown_placeholder() {
+        generate_from_pattern("Aab");
+    }
+
+    #[test]
+    fn try_generate_from_pattern_unknown_placeholder() {
+        assert_eq!(
+            try_generate_from_pattern("99-5"),
+            Err(PassgenError::UnknownPlaceholder('5'))
+        );
+    }
+
+    #[test]
+    fn try_generate_from_pattern_dangling_escape() {
+        assert_eq!(
+            try_generate_from_pattern(r"Aa\"),
+            Err(PassgenError::DanglingEscape)
+        );
+    }
+
+    #[test]
+    fn try_generate_from_pattern_empty() {
+        assert_eq!(try_generate_from_pattern(""), Err(PassgenError::ZeroLength));
+    }
+
+    fn named_pools() -> HashMap<String, Pool> {
+        let mut pools = HashMap::new();
+        pools.insert(String::from("upper"), Pool::uppercase());
+        pools.insert(String::from("digit"), Pool::digits());
+        pools.insert(String::from("vowel"), "aeiou".parse().unwrap());
+        pools.insert(String::from("none"), Pool::new());
+        pools
+    }
+
+    #[test]
+    fn generate_from_mask_positions() {
+        let password = generate_from_mask("{upper}{vowel}{vowel}-{digit}", &named_pools()).unwrap();
+        let chars: Vec<char> = password.chars().collect();
+
+        assert_eq!(chars.len(), 5);
+        assert!(chars[0].is_ascii_uppercase());
+        assert!(chars[1..3].iter().all(|ch| "aeiou".contains(*ch)));
+        assert_eq!(chars[3], '-');
+        assert!(chars[4].is_ascii_digit());
+    }
+
+    #[test]
+    fn generate_from_mask_escapes() {
+        assert_eq!(
+            generate_from_mask(r"\{upper}\\é", &named_pools()),
+            Ok(String::from(r"{upper}\é"))
+        );
+    }
+
+    #[test]
+    fn generate_from_mask_errors() {
+        let pools = named_pools();
+        let cases = [
+            ("", PassgenError::ZeroLength),
+            (
+                "{upper}{lower}",
+                PassgenError::UnknownPoolName(String::from("lower")),
+            ),
+            ("{digit}{none}", PassgenError::EmptyPool),
+            ("{digit", PassgenError::UnclosedPlaceholder),
+            (r"{digit}\", PassgenError::DanglingEscape),
+        ];
+
+        for (mask, error) in cases {
+            assert_eq!(generate_from_mask(mask, &pools), Err(error));
+        }
+    }
+}
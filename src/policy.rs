@@ -0,0 +1,2367 @@
+use crate::blocklist::retry;
+use crate::keyboard::detect_keyboard_walk;
+use crate::matcher::{lowercase, SubstringMatcher};
+use crate::repeats::sample_no_repeats;
+use crate::requirements::{pick, place_and_shuffle};
+use crate::{contains_sequence, Pool, DEFAULT_LENGTH, DEFAULT_MAX_ATTEMPTS};
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Character classes a [`Policy`] can require
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ClassKind {
+    /// ASCII lowercase letters, see [`Pool::lowercase`]
+    Lowercase,
+    /// ASCII uppercase letters, see [`Pool::uppercase`]
+    Uppercase,
+    /// ASCII digits, see [`Pool::digits`]
+    Digit,
+    /// ASCII punctuation, see [`Pool::ascii_symbols`]
+    Symbol,
+}
+
+impl ClassKind {
+    /// Every class, in declaration order
+    pub const ALL: [ClassKind; 4] = [
+        ClassKind::Lowercase,
+        ClassKind::Uppercase,
+        ClassKind::Digit,
+        ClassKind::Symbol,
+    ];
+
+    /// Returns the pool of every char of the class
+    pub fn pool(self) -> Pool {
+        match self {
+            ClassKind::Lowercase => Pool::lowercase(),
+            ClassKind::Uppercase => Pool::uppercase(),
+            ClassKind::Digit => Pool::digits(),
+            ClassKind::Symbol => Pool::ascii_symbols(),
+        }
+    }
+
+    /// Returns true if `ch` belongs to the class
+    pub fn contains(self, ch: char) -> bool {
+        match self {
+            ClassKind::Lowercase => ch.is_ascii_lowercase(),
+            ClassKind::Uppercase => ch.is_ascii_uppercase(),
+            ClassKind::Digit => ch.is_ascii_digit(),
+            ClassKind::Symbol => ch.is_ascii_punctuation(),
+        }
+    }
+
+    /// Returns the class of `ch`, or `None` if it belongs to none
+    pub fn of(ch: char) -> Option<ClassKind> {
+        ClassKind::ALL.into_iter().find(|class| class.contains(ch))
+    }
+}
+
+impl fmt::Display for ClassKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ClassKind::Lowercase => "lowercase",
+            ClassKind::Uppercase => "uppercase",
+            ClassKind::Digit => "digit",
+            ClassKind::Symbol => "symbol",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// Errors returned by [`Policy::build`] and [`generate_with_policy`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PolicyError {
+    /// The minimum length is zero
+    ZeroLength,
+    /// The minimum length is greater than the maximum length
+    InvalidLengthRange {
+        /// Minimum length
+        min: usize,
+        /// Maximum length
+        max: usize,
+    },
+    /// The excluded chars remove every char of the pool
+    EmptyPool,
+    /// A required class has no char left in the pool
+    UnavailableClass(ClassKind),
+    /// Fewer of the classes of [`Policy::require_classes`] than required have chars left in
+    /// the pool
+    TooFewClasses {
+        /// Number of classes required
+        required: usize,
+        /// Number of classes with chars left in the pool
+        available: usize,
+    },
+    /// The required chars don't fit in the maximum length
+    RequirementsTooLong {
+        /// Number of required chars
+        required: usize,
+        /// Maximum length
+        max_length: usize,
+    },
+    /// The maximum number of consecutive identical chars is zero
+    ZeroMaxConsecutiveRepeats,
+    /// The pool has too few chars to respect the limit of consecutive identical chars
+    PoolTooSmall {
+        /// Number of chars in the pool
+        len: usize,
+        /// Minimum number of chars
+        required: usize,
+    },
+    /// Leading and trailing whitespace is forbidden but the pool only contains whitespace
+    OnlyWhitespace,
+    /// The maximum run of chars of the class is zero, or too short for the minimum length or
+    /// the required chars of the class
+    InfeasibleClassRun(ClassKind),
+    /// The minimum length of forbidden sequences is below 2, so every password has one
+    SequenceRunTooShort {
+        /// Minimum number of chars of a forbidden sequence
+        min_run: usize,
+    },
+    /// No candidate satisfied the policy within the maximum number of attempts
+    MaxAttemptsExceeded {
+        /// Number of candidates drawn
+        attempts: usize,
+    },
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::ZeroLength => write!(f, "Minimum length must be greater than zero!"),
+            PolicyError::InvalidLengthRange { min, max } => write!(
+                f,
+                "Minimum length {} is greater than maximum length {}!",
+                min, max
+            ),
+            PolicyError::EmptyPool => write!(f, "Pool contains no elements once excluded!"),
+            PolicyError::UnavailableClass(class) => {
+                write!(f, "Pool contains no {} char!", class)
+            }
+            PolicyError::TooFewClasses {
+                required,
+                available,
+            } => write!(
+                f,
+                "Pool contains chars of {} classes, at least {} are required!",
+                available, required
+            ),
+            PolicyError::RequirementsTooLong {
+                required,
+                max_length,
+            } => write!(
+                f,
+                "The {} required chars don't fit in the maximum length {}!",
+                required, max_length
+            ),
+            PolicyError::ZeroMaxConsecutiveRepeats => write!(
+                f,
+                "Maximum number of consecutive identical chars must be greater than zero!"
+            ),
+            PolicyError::PoolTooSmall { len, required } => write!(
+                f,
+                "Pool contains {} chars, at least {} are required!",
+                len, required
+            ),
+            PolicyError::OnlyWhitespace => write!(f, "Pool contains only whitespace!"),
+            PolicyError::InfeasibleClassRun(class) => {
+                write!(f, "Maximum run of {} chars can't be respected!", class)
+            }
+            PolicyError::SequenceRunTooShort { min_run } => write!(
+                f,
+                "Forbidden sequences must have at least 2 chars, got {}!",
+                min_run
+            ),
+            PolicyError::MaxAttemptsExceeded { attempts } => {
+                write!(f, "No valid password found in {} attempts!", attempts)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PolicyError {}
+
+/// Errors returned when parsing a [`Policy`] from a string. `position` is the byte offset of
+/// `token` in the parsed string.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PolicyParseError {
+    /// The pair isn't a key, an operator and a value, or uses the wrong operator for its key
+    Malformed {
+        /// Offending pair
+        token: String,
+        /// Byte offset of the pair
+        position: usize,
+    },
+    /// The key of the pair is unknown
+    UnknownKey {
+        /// Offending pair
+        token: String,
+        /// Byte offset of the pair
+        position: usize,
+    },
+    /// The value of the pair isn't a number, a range or a valid escaped string
+    InvalidValue {
+        /// Offending pair
+        token: String,
+        /// Byte offset of the pair
+        position: usize,
+    },
+    /// The key was already set by a previous pair
+    DuplicateKey {
+        /// Offending pair
+        token: String,
+        /// Byte offset of the pair
+        position: usize,
+    },
+    /// The pairs are valid but the resulting policy isn't, see [`Policy::build`]
+    Invalid(PolicyError),
+}
+
+impl fmt::Display for PolicyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyParseError::Malformed { token, position } => {
+                write!(f, "Malformed pair '{}' at position {}!", token, position)
+            }
+            PolicyParseError::UnknownKey { token, position } => {
+                write!(f, "Unknown key in '{}' at position {}!", token, position)
+            }
+            PolicyParseError::InvalidValue { token, position } => {
+                write!(f, "Invalid value in '{}' at position {}!", token, position)
+            }
+            PolicyParseError::DuplicateKey { token, position } => {
+                write!(f, "Duplicate key in '{}' at position {}!", token, position)
+            }
+            PolicyParseError::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PolicyParseError {}
+
+/// Rule of a [`Policy`] broken by a password, returned by [`Policy::validate`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// The password has fewer chars than the minimum length
+    TooShort {
+        /// Minimum length
+        min: usize,
+        /// Number of chars of the password
+        actual: usize,
+    },
+    /// The password has more chars than the maximum length
+    TooLong {
+        /// Maximum length
+        max: usize,
+        /// Number of chars of the password
+        actual: usize,
+    },
+    /// The password has fewer chars of the class than required
+    MissingClass(ClassKind),
+    /// The password has chars of fewer of the classes of [`Policy::require_classes`] than
+    /// required
+    TooFewClasses {
+        /// Number of classes required
+        required: usize,
+        /// Number of classes the password has chars of
+        actual: usize,
+    },
+    /// The password contains an excluded char
+    ForbiddenChar(char),
+    /// The password has a run of identical chars longer than allowed
+    TooManyRepeats {
+        /// Maximum number of consecutive identical chars
+        max: usize,
+        /// Number of chars of the longest run
+        actual: usize,
+    },
+    /// The password has a run of chars of the class longer than allowed
+    ClassRunTooLong {
+        /// Class of the chars
+        class: ClassKind,
+        /// Maximum number of consecutive chars of the class
+        max: usize,
+        /// Number of chars of the longest run
+        actual: usize,
+    },
+    /// The password contains a sequence, at this byte range, see
+    /// [`contains_sequence`](crate::contains_sequence)
+    ContainsSequence(Range<usize>),
+    /// The password contains a QWERTY keyboard walk, at this byte range, see
+    /// [`detect_keyboard_walk`](crate::keyboard::detect_keyboard_walk)
+    KeyboardWalk(Range<usize>),
+    /// The password starts or ends with whitespace
+    LeadingOrTrailingWhitespace,
+    /// The password is in the blocklist
+    Blocklisted,
+    /// The password contains the account name or one of its tokens
+    ContainsAccountName,
+    /// The password contains this forbidden substring, in lowercase
+    ForbiddenSubstring(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::TooShort { min, actual } => write!(
+                f,
+                "Password has {} chars, at least {} are required!",
+                actual, min
+            ),
+            Violation::TooLong { max, actual } => write!(
+                f,
+                "Password has {} chars, at most {} are allowed!",
+                actual, max
+            ),
+            Violation::MissingClass(class) => {
+                write!(f, "Password doesn't have enough {} chars!", class)
+            }
+            Violation::TooFewClasses { required, actual } => write!(
+                f,
+                "Password has chars of {} classes, at least {} are required!",
+                actual, required
+            ),
+            Violation::ForbiddenChar(ch) => write!(f, "Password contains forbidden char '{}'!", ch),
+            Violation::TooManyRepeats { max, actual } => write!(
+                f,
+                "Password repeats a char {} times in a row, at most {} are allowed!",
+                actual, max
+            ),
+            Violation::ClassRunTooLong { class, max, actual } => write!(
+                f,
+                "Password has {} {} chars in a row, at most {} are allowed!",
+                actual, class, max
+            ),
+            Violation::ContainsSequence(range) => write!(
+                f,
+                "Password contains a sequence at {}..{}!",
+                range.start, range.end
+            ),
+            Violation::KeyboardWalk(range) => write!(
+                f,
+                "Password contains a keyboard walk at {}..{}!",
+                range.start, range.end
+            ),
+            Violation::LeadingOrTrailingWhitespace => {
+                write!(f, "Password starts or ends with whitespace!")
+            }
+            Violation::Blocklisted => write!(f, "Password is too common!"),
+            Violation::ContainsAccountName => write!(f, "Password contains the account name!"),
+            Violation::ForbiddenSubstring(word) => {
+                write!(f, "Password contains forbidden word '{}'!", word)
+            }
+        }
+    }
+}
+
+/// Password policy, both the rules a password must follow and how to generate one, configured
+/// with a fluent builder and checked by [`Policy::build`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use libpassgen::{ClassKind, Policy, generate_with_policy};
+/// let policy = Policy::new()
+///     .min_length(12)
+///     .max_length(16)
+///     .require(ClassKind::Digit, 2)
+///     .require(ClassKind::Symbol, 1)
+///     .exclude_chars("l1IO0")
+///     .max_consecutive_repeats(2)
+///     .build()
+///     .unwrap();
+///
+/// let password = generate_with_policy(&policy, &mut rand::thread_rng()).unwrap();
+/// let length = password.chars().count();
+///
+/// assert!((12..=16).contains(&length));
+/// assert!(password.chars().filter(|ch| ch.is_ascii_digit()).count() >= 2);
+/// # }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Policy {
+    min_length: usize,
+    max_length: usize,
+    pool: Pool,
+    required: Vec<(ClassKind, usize)>,
+    class_choice: Option<(usize, Vec<ClassKind>)>,
+    excluded: Pool,
+    max_consecutive_repeats: Option<usize>,
+    max_class_runs: Vec<(ClassKind, usize)>,
+    sequence_run: Option<usize>,
+    avoid_keyboard_walks: bool,
+    forbid_leading_trailing_whitespace: bool,
+    blocklist: BTreeSet<String>,
+    target_entropy: Option<u32>,
+    account_tokens: BTreeSet<String>,
+    forbidden_substrings: BTreeSet<String>,
+    matcher: SubstringMatcher,
+    max_attempts: usize,
+}
+
+/// Minimum number of keys of the walks forbidden by [`Policy::avoid_keyboard_walks`]
+const KEYBOARD_WALK_LENGTH: usize = 4;
+
+/// Chars splitting an account name into tokens, like Active Directory does for display names
+const ACCOUNT_NAME_DELIMITERS: [char; 7] = [',', '.', '-', '_', '#', '\t', ' '];
+
+impl Policy {
+    /// Create policy for passwords of exactly [`DEFAULT_LENGTH`] chars picked from
+    /// [`Pool::full`], without any other rule
+    pub fn new() -> Self {
+        Policy {
+            min_length: DEFAULT_LENGTH,
+            max_length: DEFAULT_LENGTH,
+            pool: Pool::full(),
+            required: Vec::new(),
+            class_choice: None,
+            excluded: Pool::new(),
+            max_consecutive_repeats: None,
+            max_class_runs: Vec::new(),
+            sequence_run: None,
+            avoid_keyboard_walks: false,
+            forbid_leading_trailing_whitespace: false,
+            blocklist: BTreeSet::new(),
+            target_entropy: None,
+            account_tokens: BTreeSet::new(),
+            forbidden_substrings: BTreeSet::new(),
+            matcher: SubstringMatcher::default(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Create policy following the memorized secrets guidance of NIST SP 800-63B:
+    ///
+    /// - at least 8 chars (section 5.1.1.1)
+    /// - at most 64 chars, the minimum maximum verifiers should accept (5.1.1.2)
+    /// - no composition rule such as required classes (5.1.1.2)
+    /// - no excluded char, since every printing ASCII char and space should be accepted
+    ///   (5.1.1.2). Generated passwords use [`Pool::full`], without space
+    /// - an empty blocklist, to be filled with [`Policy::blocklist`] from a list of common,
+    ///   expected or compromised passwords (5.1.1.2)
+    ///
+    /// Generated passwords are long enough for an entropy of 128 bits, 20 chars, rather than
+    /// the 8 chars minimum. Change it with [`Policy::target_entropy`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use libpassgen::{Policy, generate_with_policy};
+    /// let policy = Policy::nist_800_63b().blocklist(["password", "12345678"]);
+    ///
+    /// assert!(policy.validate("correct horse battery staple").is_ok());
+    /// assert!(policy.validate("Password").is_err());
+    ///
+    /// let password = generate_with_policy(&policy, &mut rand::thread_rng()).unwrap();
+    ///
+    /// assert_eq!(password.len(), 20);
+    /// # }
+    /// ```
+    pub fn nist_800_63b() -> Self {
+        Policy::new()
+            .min_length(8)
+            .max_length(64)
+            .target_entropy(128)
+    }
+
+    /// Create policy following requirement 8.3.6 of PCI DSS v4.0: at least 12 chars, with
+    /// both letters and digits. Generated passwords are long enough for an entropy of
+    /// 128 bits, and at most 64 chars are accepted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{ClassKind, Policy, Violation};
+    /// let policy = Policy::pci_dss_v4();
+    ///
+    /// assert!(policy.validate("Tr0ub4dor&3x").is_ok());
+    /// assert_eq!(
+    ///     policy.validate("troubadourxyz"),
+    ///     Err(vec![Violation::MissingClass(ClassKind::Digit)])
+    /// );
+    /// ```
+    pub fn pci_dss_v4() -> Self {
+        Policy::new()
+            .min_length(12)
+            .max_length(64)
+            .require(ClassKind::Digit, 1)
+            .require_classes(1, &[ClassKind::Lowercase, ClassKind::Uppercase])
+            .target_entropy(128)
+    }
+
+    /// Create policy following the Active Directory password complexity rules: chars of at
+    /// least 3 of the 4 classes, and when `account_name` is given, none of the tokens of at
+    /// least 3 chars of the name, compared case-insensitively. See
+    /// [`Policy::forbid_account_name`].
+    ///
+    /// Passwords have at least 7 chars, the default minimum of a domain, and at most 256.
+    /// Generated passwords are long enough for an entropy of 128 bits.
+    ///
+    /// Unlike Active Directory, letters outside of ASCII don't count as a class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Policy, Violation};
+    /// let policy = Policy::active_directory(Some("John.Smith"));
+    ///
+    /// assert!(policy.validate("Tr0ub4dor&3").is_ok());
+    /// assert_eq!(
+    ///     policy.validate("SMITH2024!"),
+    ///     Err(vec![Violation::ContainsAccountName])
+    /// );
+    /// ```
+    pub fn active_directory(account_name: Option<&str>) -> Self {
+        let policy = Policy::new()
+            .min_length(7)
+            .max_length(256)
+            .require_classes(3, &ClassKind::ALL)
+            .target_entropy(128);
+
+        match account_name {
+            Some(name) => policy.forbid_account_name(name),
+            None => policy,
+        }
+    }
+
+    /// Set the minimum number of chars
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+
+        self
+    }
+
+    /// Set the maximum number of chars. Generated passwords have a length picked uniformly
+    /// between the minimum and the maximum.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+
+        self
+    }
+
+    /// Replace the pool chars are picked from
+    pub fn pool(mut self, pool: &Pool) -> Self {
+        self.pool = pool.clone();
+
+        self
+    }
+
+    /// Require at least `min_count` chars of `class`. Calling this again for the same class
+    /// replaces its count.
+    pub fn require(mut self, class: ClassKind, min_count: usize) -> Self {
+        match self.required.iter_mut().find(|(kind, _)| *kind == class) {
+            Some((_, count)) => *count = min_count,
+            None => self.required.push((class, min_count)),
+        }
+
+        self
+    }
+
+    /// Require chars of at least `min` different classes among `classes`, for example 3 of the
+    /// 4 classes. Generated passwords get one char of each of `min` classes picked at random.
+    /// Calling this again replaces the rule.
+    pub fn require_classes(mut self, min: usize, classes: &[ClassKind]) -> Self {
+        let mut unique = Vec::new();
+        for class in classes {
+            if !unique.contains(class) {
+                unique.push(*class);
+            }
+        }
+        self.class_choice = Some((min, unique));
+
+        self
+    }
+
+    /// Forbid passwords containing `name`, or one of its tokens split on `,.-_#`, tab and
+    /// space, compared case-insensitively. Like Active Directory, the name and the tokens of
+    /// less than 3 chars are ignored, so `"J. Smith"` only forbids `j. smith` and `smith`.
+    /// Calling this several times accumulates the names.
+    pub fn forbid_account_name(mut self, name: &str) -> Self {
+        let name = name.to_lowercase();
+        let tokens = core::iter::once(name.as_str())
+            .chain(name.split(|ch| ACCOUNT_NAME_DELIMITERS.contains(&ch)))
+            .filter(|token| token.chars().count() >= 3)
+            .map(String::from);
+        self.account_tokens.extend(tokens);
+
+        self
+    }
+
+    /// Forbid the chars of `chars`, even if they are in the pool.
+    /// Calling this several times accumulates the exclusions.
+    pub fn exclude_chars(mut self, chars: &str) -> Self {
+        self.excluded.extend_from_string(chars);
+
+        self
+    }
+
+    /// Forbid runs of more than `max` identical chars, so `2` accepts `aa` but not `aaa`
+    pub fn max_consecutive_repeats(mut self, max: usize) -> Self {
+        self.max_consecutive_repeats = Some(max);
+
+        self
+    }
+
+    /// Forbid runs of more than `max` consecutive chars of `class`, so `ClassKind::Digit` with
+    /// `3` accepts `a123b` but not `a1234`. Calling this again for the same class replaces its
+    /// limit.
+    ///
+    /// Generated chars which would make a run too long are drawn again among the chars of the
+    /// other classes. This slightly favors those chars, and candidates which lose required
+    /// chars this way are rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use libpassgen::{ClassKind, Policy, generate_with_policy};
+    /// let policy = Policy::new()
+    ///     .min_length(8)
+    ///     .max_class_run(ClassKind::Symbol, 2)
+    ///     .max_class_run(ClassKind::Digit, 3);
+    ///
+    /// assert!(policy.validate("Kq3$$wM!").is_ok());
+    /// assert!(policy.validate("Kq3$$$$wM").is_err());
+    ///
+    /// let password = generate_with_policy(&policy, &mut rand::thread_rng()).unwrap();
+    ///
+    /// assert!(policy.validate(&password).is_ok());
+    /// # }
+    /// ```
+    pub fn max_class_run(mut self, class: ClassKind, max: usize) -> Self {
+        match self
+            .max_class_runs
+            .iter_mut()
+            .find(|(kind, _)| *kind == class)
+        {
+            Some((_, limit)) => *limit = max,
+            None => self.max_class_runs.push((class, max)),
+        }
+
+        self
+    }
+
+    /// Forbid sequences of at least `min_run` chars, like `abcd`, `4321` or `qwer`, as found by
+    /// [`contains_sequence`](crate::contains_sequence). Generated candidates containing one
+    /// are rejected and drawn again.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Policy, Violation};
+    /// let policy = Policy::new().min_length(8).forbid_sequences(4);
+    ///
+    /// assert!(policy.validate("x9QWEty!").is_ok());
+    /// assert_eq!(
+    ///     policy.validate("x9QWERty"),
+    ///     Err(vec![Violation::ContainsSequence(2..8)])
+    /// );
+    /// ```
+    pub fn forbid_sequences(mut self, min_run: usize) -> Self {
+        self.sequence_run = Some(min_run);
+
+        self
+    }
+
+    /// Forbid walks of at least 4 neighbouring keys on a QWERTY keyboard, like `1qaz` or
+    /// `zse4`, when `enabled` is true. See [`keyboard`](crate::keyboard). Generated candidates
+    /// containing one are rejected and drawn again.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Policy, Violation};
+    /// let policy = Policy::new().min_length(8).avoid_keyboard_walks(true);
+    ///
+    /// assert!(policy.validate("x7?qaz-k").is_ok());
+    /// assert_eq!(
+    ///     policy.validate("x7!QAZ-k"),
+    ///     Err(vec![Violation::KeyboardWalk(2..6)])
+    /// );
+    /// ```
+    pub fn avoid_keyboard_walks(mut self, enabled: bool) -> Self {
+        self.avoid_keyboard_walks = enabled;
+
+        self
+    }
+
+    /// Forbid the same char twice in a row when `enabled` is true, like
+    /// [`Policy::max_consecutive_repeats`] with `1`, or remove the limit when false.
+    ///
+    /// Without required classes, each generated char is drawn among the chars other than the
+    /// previous one, so no candidate is rejected. The entropy loss is described in
+    /// [`generate_password_no_repeats`](crate::generate_password_no_repeats).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use libpassgen::{Policy, generate_with_policy};
+    /// let policy = Policy::new()
+    ///     .pool(&"ab".parse().unwrap())
+    ///     .min_length(8)
+    ///     .max_length(8)
+    ///     .no_consecutive_repeats(true);
+    /// let password = generate_with_policy(&policy, &mut rand::thread_rng()).unwrap();
+    ///
+    /// assert!(password == "abababab" || password == "babababa");
+    /// # }
+    /// ```
+    pub fn no_consecutive_repeats(mut self, enabled: bool) -> Self {
+        self.max_consecutive_repeats = if enabled { Some(1) } else { None };
+
+        self
+    }
+
+    /// Forbid whitespace as first or last char when `enabled` is true, since it is easily
+    /// lost when a password is copied
+    pub fn forbid_leading_trailing_whitespace(mut self, enabled: bool) -> Self {
+        self.forbid_leading_trailing_whitespace = enabled;
+
+        self
+    }
+
+    /// Reject the passwords of `words`, compared case-insensitively with the whole password,
+    /// for example a list of common or compromised passwords. Calling this several times
+    /// accumulates the words.
+    pub fn blocklist<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.blocklist
+            .extend(words.into_iter().map(|word| word.as_ref().to_lowercase()));
+
+        self
+    }
+
+    /// Reject the passwords containing one of `words`, compared case-insensitively, for
+    /// example dictionary words such as `"pass"` or `"god"`. Empty words are ignored and
+    /// calling this several times accumulates the words.
+    ///
+    /// The words are compiled into an Aho-Corasick automaton, so checking a password takes a
+    /// single pass over it whatever the number of words. Generated candidates containing one
+    /// are rejected and drawn again, which gets slow for many short words.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Policy, Violation};
+    /// let policy = Policy::new().min_length(8).forbid_substrings(["pass", "god"]);
+    ///
+    /// assert_eq!(policy.contains_forbidden("x7GODz!k"), Some("god"));
+    /// assert_eq!(
+    ///     policy.validate("x7GODz!k"),
+    ///     Err(vec![Violation::ForbiddenSubstring(String::from("god"))])
+    /// );
+    /// ```
+    pub fn forbid_substrings<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.forbidden_substrings
+            .extend(words.into_iter().map(|word| lowercase(word.as_ref())));
+        self.matcher = SubstringMatcher::new(&self.forbidden_substrings);
+
+        self
+    }
+
+    /// Returns the first word of [`Policy::forbid_substrings`] found in `password`, the one
+    /// ending first, in lowercase, or `None` if it contains none.
+    pub fn contains_forbidden(&self, password: &str) -> Option<&str> {
+        self.matcher.find(password)
+    }
+
+    /// Generate passwords of the shortest length giving at least `bits` of entropy with the
+    /// pool once excluded, instead of a random length. The length is still kept between the
+    /// minimum and maximum lengths.
+    pub fn target_entropy(mut self, bits: u32) -> Self {
+        self.target_entropy = Some(bits);
+
+        self
+    }
+
+    /// Draw at most `max_attempts` candidates in [`generate_with_policy`] instead of
+    /// [`DEFAULT_MAX_ATTEMPTS`]. Zero attempts always fail.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+
+        self
+    }
+
+    /// Check the configuration and return the policy.
+    ///
+    /// # Errors
+    /// - [`PolicyError::ZeroLength`] if the minimum length is zero
+    /// - [`PolicyError::InvalidLengthRange`] if the minimum length is greater than the maximum
+    /// - [`PolicyError::EmptyPool`] if the exclusions remove every char of the pool
+    /// - [`PolicyError::UnavailableClass`] if a required class has no char left
+    /// - [`PolicyError::TooFewClasses`] if too few of the classes of
+    ///   [`Policy::require_classes`] have chars left
+    /// - [`PolicyError::RequirementsTooLong`] if the required counts add up to more than the
+    ///   maximum length
+    /// - [`PolicyError::ZeroMaxConsecutiveRepeats`] if the maximum run of identical chars is
+    ///   zero
+    /// - [`PolicyError::PoolTooSmall`] if a single char is left and it can't be repeated
+    ///   enough, or an entropy is targeted
+    /// - [`PolicyError::OnlyWhitespace`] if only whitespace is left while it is forbidden at
+    ///   both ends
+    /// - [`PolicyError::InfeasibleClassRun`] if a maximum class run is zero, only chars of the
+    ///   class are left and the minimum length is longer than the run, or the required chars
+    ///   of the class can't be separated within the maximum length
+    /// - [`PolicyError::SequenceRunTooShort`] if forbidden sequences have fewer than 2 chars
+    pub fn build(self) -> Result<Self, PolicyError> {
+        self.available()?;
+
+        Ok(self)
+    }
+
+    /// Validated pool of the chars left once excluded
+    fn available(&self) -> Result<Pool, PolicyError> {
+        if self.min_length == 0 {
+            return Err(PolicyError::ZeroLength);
+        }
+        if self.min_length > self.max_length {
+            return Err(PolicyError::InvalidLengthRange {
+                min: self.min_length,
+                max: self.max_length,
+            });
+        }
+
+        let available = self.pool.difference(&self.excluded);
+        if available.is_empty() {
+            return Err(PolicyError::EmptyPool);
+        }
+        for (class, count) in &self.required {
+            if *count > 0 && !available.iter().any(|ch| class.contains(*ch)) {
+                return Err(PolicyError::UnavailableClass(*class));
+            }
+        }
+        if let Some((min, classes)) = &self.class_choice {
+            let count = classes
+                .iter()
+                .filter(|class| available.iter().any(|ch| class.contains(*ch)))
+                .count();
+            if count < *min {
+                return Err(PolicyError::TooFewClasses {
+                    required: *min,
+                    available: count,
+                });
+            }
+        }
+        let required = self.required_count();
+        if required > self.max_length {
+            return Err(PolicyError::RequirementsTooLong {
+                required,
+                max_length: self.max_length,
+            });
+        }
+        match self.max_consecutive_repeats {
+            Some(0) => return Err(PolicyError::ZeroMaxConsecutiveRepeats),
+            Some(max) if available.len() == 1 && max < self.min_length => {
+                return Err(PolicyError::PoolTooSmall {
+                    len: 1,
+                    required: 2,
+                })
+            }
+            _ => {}
+        }
+        if self.target_entropy.is_some() && available.len() == 1 {
+            return Err(PolicyError::PoolTooSmall {
+                len: 1,
+                required: 2,
+            });
+        }
+        if self.forbid_leading_trailing_whitespace && available.iter().all(|ch| ch.is_whitespace())
+        {
+            return Err(PolicyError::OnlyWhitespace);
+        }
+        for (class, max) in &self.max_class_runs {
+            let required = self
+                .required
+                .iter()
+                .find(|(kind, _)| kind == class)
+                .map_or(0, |(_, count)| *count);
+            // Each run of `max` required chars but the last needs one other char after it
+            let separated = match required {
+                0 => 0,
+                _ => required.saturating_add((required - 1) / (*max).max(1)),
+            };
+            if *max == 0
+                || (available.iter().all(|ch| class.contains(*ch)) && self.min_length > *max)
+                || separated > self.max_length
+            {
+                return Err(PolicyError::InfeasibleClassRun(*class));
+            }
+        }
+        match self.sequence_run {
+            Some(min_run) if min_run < 2 => {
+                return Err(PolicyError::SequenceRunTooShort { min_run })
+            }
+            _ => {}
+        }
+
+        Ok(available)
+    }
+
+    /// Number of required chars, including one per class of [`Policy::require_classes`]
+    /// not already required
+    fn required_count(&self) -> usize {
+        self.required
+            .iter()
+            .fold(0usize, |sum, (_, count)| sum.saturating_add(*count))
+            .saturating_add(self.extra_classes().1)
+    }
+
+    /// Classes of [`Policy::require_classes`] not already required, and how many of them
+    /// generated passwords need
+    fn extra_classes(&self) -> (Vec<ClassKind>, usize) {
+        let (min, classes) = match &self.class_choice {
+            Some((min, classes)) => (*min, classes),
+            None => return (Vec::new(), 0),
+        };
+        let (required, extra): (Vec<ClassKind>, Vec<ClassKind>) =
+            classes.iter().partition(|class| {
+                self.required
+                    .iter()
+                    .any(|(kind, count)| kind == *class && *count > 0)
+            });
+        let needed = min.saturating_sub(required.len());
+
+        (extra, needed)
+    }
+
+    /// Check a password, for example one chosen by a user, against every rule of the policy.
+    /// Lengths are counted in chars, not bytes.
+    ///
+    /// The pool only tells which chars are generated: a password may contain chars outside of
+    /// it, but not the excluded ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{ClassKind, Policy, Violation};
+    /// let policy = Policy::new()
+    ///     .min_length(8)
+    ///     .max_length(64)
+    ///     .require(ClassKind::Digit, 1);
+    ///
+    /// assert_eq!(policy.validate("correct horse 1"), Ok(()));
+    /// assert_eq!(
+    ///     policy.validate("hunter"),
+    ///     Err(vec![
+    ///         Violation::TooShort { min: 8, actual: 6 },
+    ///         Violation::MissingClass(ClassKind::Digit),
+    ///     ])
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns every [`Violation`] found, in the order of the variants.
+    pub fn validate(&self, password: &str) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            violations.push(Violation::TooShort {
+                min: self.min_length,
+                actual: length,
+            });
+        }
+        if length > self.max_length {
+            violations.push(Violation::TooLong {
+                max: self.max_length,
+                actual: length,
+            });
+        }
+        for (class, count) in &self.required {
+            if password.chars().filter(|ch| class.contains(*ch)).count() < *count {
+                violations.push(Violation::MissingClass(*class));
+            }
+        }
+        if let Some((min, classes)) = &self.class_choice {
+            let actual = classes
+                .iter()
+                .filter(|class| password.chars().any(|ch| class.contains(ch)))
+                .count();
+            if actual < *min {
+                violations.push(Violation::TooFewClasses {
+                    required: *min,
+                    actual,
+                });
+            }
+        }
+        let mut forbidden = Pool::new();
+        forbidden.extend(password.chars().filter(|ch| self.excluded.contains(*ch)));
+        violations.extend(forbidden.chars().map(Violation::ForbiddenChar));
+        if let Some(max) = self.max_consecutive_repeats {
+            let longest = longest_run(password);
+            if longest > max {
+                violations.push(Violation::TooManyRepeats {
+                    max,
+                    actual: longest,
+                });
+            }
+        }
+        for (class, max) in &self.max_class_runs {
+            let longest = longest_class_run(password, *class);
+            if longest > *max {
+                violations.push(Violation::ClassRunTooLong {
+                    class: *class,
+                    max: *max,
+                    actual: longest,
+                });
+            }
+        }
+        if let Some(range) = self
+            .sequence_run
+            .and_then(|min_run| contains_sequence(password, min_run))
+        {
+            violations.push(Violation::ContainsSequence(range));
+        }
+        if self.avoid_keyboard_walks {
+            let walks = detect_keyboard_walk(password, KEYBOARD_WALK_LENGTH);
+            violations.extend(walks.into_iter().take(1).map(Violation::KeyboardWalk));
+        }
+        if self.forbid_leading_trailing_whitespace
+            && (password.starts_with(char::is_whitespace)
+                || password.ends_with(char::is_whitespace))
+        {
+            violations.push(Violation::LeadingOrTrailingWhitespace);
+        }
+        if !self.blocklist.is_empty() && self.blocklist.contains(&password.to_lowercase()) {
+            violations.push(Violation::Blocklisted);
+        }
+        if !self.account_tokens.is_empty() {
+            let lowercase = password.to_lowercase();
+            if self
+                .account_tokens
+                .iter()
+                .any(|token| lowercase.contains(token.as_str()))
+            {
+                violations.push(Violation::ContainsAccountName);
+            }
+        }
+        if let Some(word) = self.contains_forbidden(password) {
+            violations.push(Violation::ForbiddenSubstring(String::from(word)));
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::new()
+    }
+}
+
+/// Parses comma separated pairs, each key at most once, starting from [`Policy::new`]:
+///
+/// - `len=16` or `len=16..32`, the minimum and maximum lengths, both inclusive
+/// - `lower>=1`, `upper>=1`, `digit>=2` and `symbol>=1`, see [`Policy::require`]
+/// - `classes>=3:lower+upper+digit+symbol`, see [`Policy::require_classes`]
+/// - `repeat<=2`, see [`Policy::max_consecutive_repeats`]
+/// - `lower-run<=3`, `upper-run<=3`, `digit-run<=3` and `symbol-run<=2`, see
+///   [`Policy::max_class_run`]
+/// - `no-sequence=4`, see [`Policy::forbid_sequences`]
+/// - `no-edge-space=1` or `0`, see [`Policy::forbid_leading_trailing_whitespace`]
+/// - `no-keyboard-walk=1` or `0`, see [`Policy::avoid_keyboard_walks`]
+/// - `entropy=128`, see [`Policy::target_entropy`]
+/// - `attempts=1000`, see [`Policy::max_attempts`]
+/// - `block=password|letmein`, see [`Policy::blocklist`]
+/// - `account=jdoe|john|doe`, see [`Policy::forbid_account_name`]
+/// - `forbid=pass|god`, see [`Policy::forbid_substrings`]
+/// - `pool=abc123`, see [`Policy::pool`]
+/// - `exclude=l1IO0`, see [`Policy::exclude_chars`]
+///
+/// In a value, a backslash escapes the next char, so `exclude=\,\\` excludes a comma and a
+/// backslash and `block=a\|b` blocks `a|b`. The words of a list are separated by `|`.
+///
+/// The empty string gives [`Policy::new`], and the policy is checked with [`Policy::build`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{ClassKind, Policy};
+/// let policy: Policy = "len=16..32,lower>=1,upper>=1,digit>=2,symbol>=1,exclude=l1IO0"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(
+///     policy,
+///     Policy::new()
+///         .min_length(16)
+///         .max_length(32)
+///         .require(ClassKind::Lowercase, 1)
+///         .require(ClassKind::Uppercase, 1)
+///         .require(ClassKind::Digit, 2)
+///         .require(ClassKind::Symbol, 1)
+///         .exclude_chars("l1IO0")
+/// );
+/// ```
+impl FromStr for Policy {
+    type Err = PolicyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = Policy::new();
+        let mut seen: Vec<&str> = Vec::new();
+
+        for (position, token) in split_pairs(s) {
+            let error = |make: fn(String, usize) -> PolicyParseError| make(token.into(), position);
+            let (key, operator, value) = split_pair(token).ok_or_else(|| error(malformed))?;
+            let expected = match key {
+                "len" | "no-sequence" | "no-edge-space" | "no-keyboard-walk" | "entropy"
+                | "attempts" | "block" | "account" | "forbid" | "pool" | "exclude" => "=",
+                "lower" | "upper" | "digit" | "symbol" | "classes" => ">=",
+                "repeat" | "lower-run" | "upper-run" | "digit-run" | "symbol-run" => "<=",
+                _ => return Err(error(unknown_key)),
+            };
+            if operator != expected {
+                return Err(error(malformed));
+            }
+            if seen.contains(&key) {
+                return Err(error(duplicate_key));
+            }
+            seen.push(key);
+
+            let number = |value: &str| value.parse::<usize>().map_err(|_| error(invalid_value));
+            let text = |value: &str| unescape(value).ok_or_else(|| error(invalid_value));
+            let list = |value: &str| split_list(value).ok_or_else(|| error(invalid_value));
+            let flag = |value: &str| match value {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                _ => Err(error(invalid_value)),
+            };
+            policy = match key {
+                "len" => match value.split_once("..") {
+                    Some((min, max)) => policy.min_length(number(min)?).max_length(number(max)?),
+                    None => {
+                        let length = number(value)?;
+                        policy.min_length(length).max_length(length)
+                    }
+                },
+                "entropy" => {
+                    policy.target_entropy(value.parse().map_err(|_| error(invalid_value))?)
+                }
+                "classes" => {
+                    let (min, classes) = value.split_once(':').ok_or_else(|| error(malformed))?;
+                    let classes = match classes {
+                        "" => Vec::new(),
+                        _ => classes
+                            .split('+')
+                            .map(|key| class_from_key(key).ok_or_else(|| error(invalid_value)))
+                            .collect::<Result<_, _>>()?,
+                    };
+                    policy.require_classes(number(min)?, &classes)
+                }
+                "no-edge-space" => policy.forbid_leading_trailing_whitespace(flag(value)?),
+                "no-keyboard-walk" => policy.avoid_keyboard_walks(flag(value)?),
+                "block" => policy.blocklist(list(value)?),
+                "account" => list(value)?
+                    .iter()
+                    .fold(policy, |policy, token| policy.forbid_account_name(token)),
+                "forbid" => policy.forbid_substrings(list(value)?),
+                "pool" => policy.pool(&text(value)?.chars().collect()),
+                "exclude" => policy.exclude_chars(&text(value)?),
+                "repeat" => policy.max_consecutive_repeats(number(value)?),
+                "attempts" => policy.max_attempts(number(value)?),
+                "no-sequence" => policy.forbid_sequences(number(value)?),
+                _ => match key.strip_suffix("-run") {
+                    Some(class) => {
+                        policy.max_class_run(class_from_key(class).unwrap(), number(value)?)
+                    }
+                    None => policy.require(class_from_key(key).unwrap(), number(value)?),
+                },
+            };
+        }
+
+        policy.build().map_err(PolicyParseError::Invalid)
+    }
+}
+
+/// Writes the pairs parsed by the [`FromStr`] implementation, so every policy round-trips.
+/// The rules left to their default, including a [`Pool::full`] pool, are not written.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{ClassKind, Policy};
+/// let policy = Policy::new()
+///     .max_length(20)
+///     .require(ClassKind::Digit, 2)
+///     .exclude_chars("O0,");
+///
+/// assert_eq!(policy.to_string(), "len=16..20,digit>=2,exclude=O0\\,");
+/// assert_eq!(policy.to_string().parse(), Ok(policy));
+/// ```
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.min_length == self.max_length {
+            write!(f, "len={}", self.min_length)?;
+        } else {
+            write!(f, "len={}..{}", self.min_length, self.max_length)?;
+        }
+        for (class, count) in &self.required {
+            write!(f, ",{}>={}", class_key(*class), count)?;
+        }
+        if let Some((min, classes)) = &self.class_choice {
+            let keys: Vec<&str> = classes.iter().map(|class| class_key(*class)).collect();
+            write!(f, ",classes>={}:{}", min, keys.join("+"))?;
+        }
+        if let Some(max) = self.max_consecutive_repeats {
+            write!(f, ",repeat<={}", max)?;
+        }
+        for (class, max) in &self.max_class_runs {
+            write!(f, ",{}-run<={}", class_key(*class), max)?;
+        }
+        if let Some(min_run) = self.sequence_run {
+            write!(f, ",no-sequence={}", min_run)?;
+        }
+        if self.forbid_leading_trailing_whitespace {
+            f.write_str(",no-edge-space=1")?;
+        }
+        if self.avoid_keyboard_walks {
+            f.write_str(",no-keyboard-walk=1")?;
+        }
+        if let Some(bits) = self.target_entropy {
+            write!(f, ",entropy={}", bits)?;
+        }
+        if self.max_attempts != DEFAULT_MAX_ATTEMPTS {
+            write!(f, ",attempts={}", self.max_attempts)?;
+        }
+        write_list(f, "block", &self.blocklist)?;
+        write_list(f, "account", &self.account_tokens)?;
+        write_list(f, "forbid", &self.forbidden_substrings)?;
+        if self.pool != Pool::full() {
+            f.write_str(",pool=")?;
+            write_escaped(f, &self.pool.iter().collect::<String>())?;
+        }
+        if !self.excluded.is_empty() {
+            f.write_str(",exclude=")?;
+            write_escaped(f, &self.excluded.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Key of `class` in the string form of a [`Policy`]
+fn class_key(class: ClassKind) -> &'static str {
+    match class {
+        ClassKind::Lowercase => "lower",
+        ClassKind::Uppercase => "upper",
+        ClassKind::Digit => "digit",
+        ClassKind::Symbol => "symbol",
+    }
+}
+
+/// Class of a key returned by [`class_key`]
+fn class_from_key(key: &str) -> Option<ClassKind> {
+    ClassKind::ALL
+        .into_iter()
+        .find(|class| class_key(*class) == key)
+}
+
+/// Writes `,key=` and the `words` separated by `|`, unless there are none
+fn write_list(f: &mut fmt::Formatter<'_>, key: &str, words: &BTreeSet<String>) -> fmt::Result {
+    if words.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, ",{}=", key)?;
+    for (index, word) in words.iter().enumerate() {
+        if index > 0 {
+            f.write_str("|")?;
+        }
+        write_escaped(f, word)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `value`, escaping the commas, backslashes and bars with a backslash
+fn write_escaped(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    for ch in value.chars() {
+        if ch == ',' || ch == '\\' || ch == '|' {
+            f.write_str("\\")?;
+        }
+        write!(f, "{}", ch)?;
+    }
+
+    Ok(())
+}
+
+/// Splits `s` on the commas not escaped by a backslash, with the byte offset of each pair
+fn split_pairs(s: &str) -> Vec<(usize, &str)> {
+    let mut pairs = Vec::new();
+    if s.is_empty() {
+        return pairs;
+    }
+
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, ch) in s.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                pairs.push((start, &s[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    pairs.push((start, &s[start..]));
+
+    pairs
+}
+
+/// Splits `token` into its key, its operator `=`, `>=` or `<=`, and its value
+fn split_pair(token: &str) -> Option<(&str, &str, &str)> {
+    let index = token.find(['=', '<', '>'])?;
+    let (key, rest) = token.split_at(index);
+    let operator = ["=", ">=", "<="]
+        .into_iter()
+        .find(|operator| rest.starts_with(operator))?;
+
+    if key.is_empty() {
+        None
+    } else {
+        Some((key, operator, &rest[operator.len()..]))
+    }
+}
+
+/// Removes the backslashes escaping the next char, or returns `None` on a trailing backslash
+fn unescape(value: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => unescaped.push(chars.next()?),
+            _ => unescaped.push(ch),
+        }
+    }
+
+    Some(unescaped)
+}
+
+/// Splits `value` on the bars not escaped by a backslash and unescapes each word, or returns
+/// `None` on a trailing backslash
+fn split_list(value: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => word.push(chars.next()?),
+            '|' => words.push(core::mem::take(&mut word)),
+            _ => word.push(ch),
+        }
+    }
+    words.push(word);
+
+    Some(words)
+}
+
+fn malformed(token: String, position: usize) -> PolicyParseError {
+    PolicyParseError::Malformed { token, position }
+}
+
+fn unknown_key(token: String, position: usize) -> PolicyParseError {
+    PolicyParseError::UnknownKey { token, position }
+}
+
+fn invalid_value(token: String, position: usize) -> PolicyParseError {
+    PolicyParseError::InvalidValue { token, position }
+}
+
+fn duplicate_key(token: String, position: usize) -> PolicyParseError {
+    PolicyParseError::DuplicateKey { token, position }
+}
+
+/// Generate random password following `policy`, using the given random number generator.
+///
+/// The length is picked uniformly between the minimum and maximum lengths, at least the number
+/// of required chars, unless an entropy is targeted with [`Policy::target_entropy`]. The
+/// required chars, including one char of each of the classes picked for
+/// [`Policy::require_classes`], are drawn from their class, restricted to the pool without the
+/// excluded chars, the other positions from that pool, then the positions are shuffled.
+/// Candidates breaking the other rules are rejected and drawn again, up to
+/// [`DEFAULT_MAX_ATTEMPTS`] times or the number set with [`Policy::max_attempts`].
+///
+/// With [`Policy::no_consecutive_repeats`] and no required chars, the candidates are drawn
+/// without consecutive repeats instead. With [`Policy::max_class_run`], the chars which would
+/// make a run too long are then drawn again from the other classes.
+///
+/// # Errors
+/// Same as [`Policy::build`], and [`PolicyError::MaxAttemptsExceeded`] if every candidate
+/// was rejected.
+pub fn generate_with_policy<R: Rng + ?Sized>(
+    policy: &Policy,
+    rng: &mut R,
+) -> Result<String, PolicyError> {
+    let available = policy.available()?;
+    let (extra, needed) = policy.extra_classes();
+    let extra: Vec<ClassKind> = extra
+        .into_iter()
+        .filter(|class| available.iter().any(|ch| class.contains(*ch)))
+        .collect();
+    let required: Vec<(Pool, usize)> = policy
+        .required
+        .iter()
+        .copied()
+        .chain(extra.choose_multiple(rng, needed).map(|class| (*class, 1)))
+        .map(|(class, count)| (available.filtered(|ch| class.contains(ch)), count))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    let required: Vec<(&Pool, usize)> = required
+        .iter()
+        .map(|(pool, count)| (pool, *count))
+        .collect();
+    let min_length = policy.min_length.max(policy.required_count());
+    let lengths = match policy.target_entropy {
+        Some(bits) => {
+            let length = length_for_entropy(bits, available.len(), policy.max_length);
+            let length = length.max(min_length);
+
+            length..=length
+        }
+        None => min_length..=policy.max_length,
+    };
+
+    let no_repeats =
+        policy.max_consecutive_repeats == Some(1) && required.is_empty() && available.len() >= 2;
+
+    let class_runs: Vec<(ClassKind, usize, Pool)> = policy
+        .max_class_runs
+        .iter()
+        .map(|(class, max)| (*class, *max, available.filtered(|ch| !class.contains(ch))))
+        .collect();
+
+    let candidate = || {
+        let length = rng.gen_range(lengths.clone());
+        let password = if no_repeats {
+            sample_no_repeats(&available, length, rng)
+        } else {
+            place_and_shuffle(&required, &available, length, rng)
+        };
+        if class_runs.is_empty() {
+            password
+        } else {
+            limit_class_runs(&password, &class_runs, rng)
+        }
+    };
+
+    retry(policy.max_attempts, candidate, |password| {
+        policy.validate(password).is_ok()
+    })
+    .ok_or(PolicyError::MaxAttemptsExceeded {
+        attempts: policy.max_attempts,
+    })
+}
+
+/// Smallest length `l` such that `pool_size^l >= 2^bits`, or `max_length` if it is smaller.
+///
+/// `pool_size^l` is kept as a 64 bits fixed point mantissa in `[1, 2)` and a count of the
+/// powers of two shifted out of it. Since `bits` is an integer, the entropy is reached as soon
+/// as that count is, whatever the mantissa. `pool_size` must be at least 2.
+fn length_for_entropy(bits: u32, pool_size: usize, max_length: usize) -> usize {
+    const ONE: u128 = 1 << 64;
+    let mut mantissa = ONE;
+    let mut shifted = 0;
+
+    for length in 1..max_length {
+        mantissa *= pool_size as u128;
+        while mantissa >= 2 * ONE {
+            mantissa >>= 1;
+            shifted += 1;
+        }
+        if shifted >= bits {
+            return length;
+        }
+    }
+
+    max_length
+}
+
+/// Replaces each char which would make a run of its class longer than the limit of
+/// `class_runs` by a random char of the other classes, kept with each limit
+fn limit_class_runs<R: Rng + ?Sized>(
+    password: &str,
+    class_runs: &[(ClassKind, usize, Pool)],
+    rng: &mut R,
+) -> String {
+    let mut limited = String::with_capacity(password.len());
+    let mut run: Option<(ClassKind, usize)> = None;
+    for mut ch in password.chars() {
+        if let (Some(class), Some((current, length))) = (ClassKind::of(ch), run) {
+            let limit = class_runs.iter().find(|(kind, ..)| *kind == class);
+            if let Some((_, max, others)) = limit {
+                if class == current && length >= *max && !others.is_empty() {
+                    ch = pick(others, rng);
+                }
+            }
+        }
+        run = match (ClassKind::of(ch), run) {
+            (Some(class), Some((current, length))) if class == current => Some((class, length + 1)),
+            (class, _) => class.map(|class| (class, 1)),
+        };
+        limited.push(ch);
+    }
+
+    limited
+}
+
+/// Number of chars of the longest run of chars of `class`
+fn longest_class_run(password: &str, class: ClassKind) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in password.chars() {
+        run = if class.contains(ch) { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+
+    longest
+}
+
+/// Number of chars of the longest run of identical chars
+fn longest_run(password: &str) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for ch in password.chars() {
+        run = if previous == Some(ch) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        previous = Some(ch);
+    }
+
+    longest
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generate_with_policy_follows_policy() {
+        let policy = Policy::new()
+            .min_length(6)
+            .max_length(10)
+            .pool(&Pool::ascii_printable())
+            .require(ClassKind::Uppercase, 1)
+            .require(ClassKind::Digit, 2)
+            .require(ClassKind::Symbol, 1)
+            .exclude_chars("aeiou")
+            .max_consecutive_repeats(1)
+            .forbid_leading_trailing_whitespace(true)
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut lengths = [false; 11];
+
+        for _ in 0..2000 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+            let count =
+                |class: ClassKind| password.chars().filter(|ch| class.contains(*ch)).count();
+            let length = password.chars().count();
+
+            assert!((6..=10).contains(&length));
+            assert!(count(ClassKind::Uppercase) >= 1);
+            assert!(count(ClassKind::Digit) >= 2);
+            assert!(count(ClassKind::Symbol) >= 1);
+            assert!(!password.contains(|ch| "aeiou".contains(ch)));
+            assert_eq!(longest_run(&password), 1);
+            assert!(!password.starts_with(' ') && !password.ends_with(' '));
+            lengths[length] = true;
+        }
+        assert!(lengths[6..].iter().all(|seen| *seen));
+    }
+
+    #[test]
+    fn generate_with_policy_length_fits_requirements() {
+        let policy = Policy::new()
+            .min_length(1)
+            .max_length(3)
+            .require(ClassKind::Digit, 3);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert_eq!(password.len(), 3);
+            assert!(password.chars().all(|ch| ch.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn build_errors() {
+        let cases = [
+            (Policy::new().min_length(0), PolicyError::ZeroLength),
+            (
+                Policy::new().min_length(10).max_length(8),
+                PolicyError::InvalidLengthRange { min: 10, max: 8 },
+            ),
+            (
+                Policy::new()
+                    .pool(&Pool::digits())
+                    .exclude_chars("0123456789"),
+                PolicyError::EmptyPool,
+            ),
+            (
+                Policy::new()
+                    .pool(&Pool::digits())
+                    .require(ClassKind::Symbol, 1),
+                PolicyError::UnavailableClass(ClassKind::Symbol),
+            ),
+            (
+                Policy::new()
+                    .max_length(4)
+                    .min_length(4)
+                    .require(ClassKind::Digit, 3)
+                    .require(ClassKind::Symbol, 2),
+                PolicyError::RequirementsTooLong {
+                    required: 5,
+                    max_length: 4,
+                },
+            ),
+            (
+                Policy::new().max_consecutive_repeats(0),
+                PolicyError::ZeroMaxConsecutiveRepeats,
+            ),
+            (
+                Policy::new()
+                    .pool(&"a".parse().unwrap())
+                    .max_consecutive_repeats(3),
+                PolicyError::PoolTooSmall {
+                    len: 1,
+                    required: 2,
+                },
+            ),
+            (
+                Policy::new()
+                    .pool(&" \t".parse().unwrap())
+                    .forbid_leading_trailing_whitespace(true),
+                PolicyError::OnlyWhitespace,
+            ),
+        ];
+
+        for (policy, error) in cases {
+            assert_eq!(policy.clone().build(), Err(error.clone()));
+            assert_eq!(
+                generate_with_policy(&policy, &mut StdRng::seed_from_u64(42)),
+                Err(error)
+            );
+        }
+    }
+
+    #[test]
+    fn require_replaces_count() {
+        let policy = Policy::new()
+            .require(ClassKind::Digit, 5)
+            .require(ClassKind::Digit, 1);
+
+        assert_eq!(policy.required, [(ClassKind::Digit, 1)]);
+    }
+
+    /// Valid policy whose every possible password is blocklisted
+    fn unsatisfiable_policy() -> Policy {
+        Policy::new()
+            .pool(&"ab".parse().unwrap())
+            .min_length(2)
+            .max_length(2)
+            .blocklist(["aa", "ab", "ba", "bb"])
+    }
+
+    #[test]
+    fn generate_with_policy_gives_up() {
+        let policy = unsatisfiable_policy().build().unwrap();
+
+        assert_eq!(
+            generate_with_policy(&policy, &mut StdRng::seed_from_u64(42)),
+            Err(PolicyError::MaxAttemptsExceeded {
+                attempts: DEFAULT_MAX_ATTEMPTS
+            })
+        );
+    }
+
+    #[test]
+    fn no_consecutive_repeats_never_repeats() {
+        let policies = [
+            Policy::new()
+                .pool(&Pool::digits())
+                .min_length(8)
+                .max_length(32)
+                .no_consecutive_repeats(true),
+            Policy::new()
+                .pool(&"abc".parse().unwrap())
+                .min_length(16)
+                .max_length(16)
+                .no_consecutive_repeats(true),
+            Policy::new()
+                .pool(&Pool::alphanumeric())
+                .require(ClassKind::Digit, 3)
+                .no_consecutive_repeats(true),
+        ];
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for policy in policies {
+            for _ in 0..2000 {
+                let password = generate_with_policy(&policy, &mut rng).unwrap();
+                let chars: Vec<char> = password.chars().collect();
+
+                assert!(chars.windows(2).all(|pair| pair[0] != pair[1]));
+                assert_eq!(policy.validate(&password), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn no_consecutive_repeats_single_char() {
+        let policy = Policy::new()
+            .pool(&"a".parse().unwrap())
+            .no_consecutive_repeats(true);
+
+        assert_eq!(
+            policy.build(),
+            Err(PolicyError::PoolTooSmall {
+                len: 1,
+                required: 2
+            })
+        );
+
+        let policy = Policy::new()
+            .pool(&"a".parse().unwrap())
+            .min_length(1)
+            .max_length(1)
+            .no_consecutive_repeats(true);
+        assert_eq!(
+            generate_with_policy(&policy, &mut StdRng::seed_from_u64(42)),
+            Ok(String::from("a"))
+        );
+        assert_eq!(
+            policy.no_consecutive_repeats(false).max_consecutive_repeats,
+            None
+        );
+    }
+
+    #[test]
+    fn max_class_run_never_exceeded() {
+        let policies = [
+            Policy::new()
+                .pool(&Pool::ascii_printable())
+                .min_length(8)
+                .max_length(32)
+                .max_class_run(ClassKind::Symbol, 2)
+                .max_class_run(ClassKind::Digit, 3),
+            Policy::new()
+                .pool(&Pool::digits().union(&"ab".parse().unwrap()))
+                .min_length(24)
+                .max_length(24)
+                .require(ClassKind::Digit, 12)
+                .max_class_run(ClassKind::Digit, 1),
+            Policy::new()
+                .pool(&Pool::alphanumeric())
+                .max_class_run(ClassKind::Lowercase, 1)
+                .max_class_run(ClassKind::Uppercase, 1)
+                .no_consecutive_repeats(true),
+        ];
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for policy in policies {
+            for _ in 0..2000 {
+                let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+                assert_eq!(policy.validate(&password), Ok(()));
+                for (class, max) in &policy.max_class_runs {
+                    assert!(longest_class_run(&password, *class) <= *max);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_class_run_infeasible() {
+        let cases = [
+            Policy::new().max_class_run(ClassKind::Digit, 0),
+            Policy::new()
+                .pool(&Pool::digits())
+                .max_class_run(ClassKind::Digit, 4),
+            Policy::new()
+                .min_length(8)
+                .max_length(8)
+                .require(ClassKind::Digit, 5)
+                .max_class_run(ClassKind::Digit, 1),
+        ];
+
+        for policy in cases {
+            assert_eq!(
+                generate_with_policy(&policy, &mut StdRng::seed_from_u64(42)),
+                Err(PolicyError::InfeasibleClassRun(ClassKind::Digit))
+            );
+        }
+        let policy = Policy::new()
+            .min_length(9)
+            .max_length(9)
+            .require(ClassKind::Digit, 5)
+            .max_class_run(ClassKind::Digit, 1);
+        assert!(policy.build().is_ok());
+    }
+
+    #[test]
+    fn validate_class_runs() {
+        let policy = Policy::new()
+            .min_length(1)
+            .max_length(64)
+            .max_class_run(ClassKind::Symbol, 2)
+            .max_class_run(ClassKind::Digit, 3)
+            .max_class_run(ClassKind::Digit, 2);
+
+        assert_eq!(policy.validate("Kq3$$wM12"), Ok(()));
+        assert_eq!(
+            policy.validate("Kq3$$$$wM123"),
+            Err(vec![
+                Violation::ClassRunTooLong {
+                    class: ClassKind::Symbol,
+                    max: 2,
+                    actual: 4
+                },
+                Violation::ClassRunTooLong {
+                    class: ClassKind::Digit,
+                    max: 2,
+                    actual: 3
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn forbid_sequences_rejects_candidates() {
+        let policy = Policy::new()
+            .pool(&"abcdefgh".parse().unwrap())
+            .min_length(12)
+            .max_length(12)
+            .forbid_sequences(3);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..500 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert_eq!(contains_sequence(&password, 3), None);
+        }
+        assert_eq!(
+            policy.validate("hgfxxxxxxxxx"),
+            Err(vec![Violation::ContainsSequence(0..3)])
+        );
+        assert_eq!(
+            policy.forbid_sequences(1).build(),
+            Err(PolicyError::SequenceRunTooShort { min_run: 1 })
+        );
+    }
+
+    #[test]
+    fn avoid_keyboard_walks_rejects_candidates() {
+        let policy = Policy::new()
+            .pool(&"qwertasdfgzxcvb12345".parse().unwrap())
+            .min_length(10)
+            .max_length(10)
+            .avoid_keyboard_walks(true);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..500 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert!(detect_keyboard_walk(&password, 4).is_empty());
+        }
+        assert_eq!(
+            policy.validate("qwer1qaz55"),
+            Err(vec![Violation::KeyboardWalk(0..4)])
+        );
+        assert_eq!(policy.validate("qwe1qa5555"), Ok(()));
+    }
+
+    #[test]
+    fn forbid_substrings_rejects_candidates() {
+        let words = ["ab", "bca", "CAB"].map(String::from);
+        let policy = Policy::new()
+            .pool(&"abcx".parse().unwrap())
+            .min_length(8)
+            .max_length(8)
+            .forbid_substrings(words);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..500 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert_eq!(policy.contains_forbidden(&password), None);
+            assert!(!password.contains("ab") && !password.contains("bca"));
+            assert!(!password.contains("cab"));
+        }
+    }
+
+    #[test]
+    fn contains_forbidden_matches() {
+        let policy = Policy::new()
+            .forbid_substrings(["pass", "password", "word"])
+            .forbid_substrings(["GOD", ""]);
+
+        assert_eq!(policy.contains_forbidden("xPaSsWoRd"), Some("pass"));
+        assert_eq!(policy.contains_forbidden("swordfish"), Some("word"));
+        assert_eq!(policy.contains_forbidden("9g0d-GoD"), Some("god"));
+        assert_eq!(policy.contains_forbidden("pas-wor-go"), None);
+        assert_eq!(
+            Policy::new()
+                .forbid_substrings(["ΘΕΟΣ"])
+                .contains_forbidden("ΘΕΟΣ"),
+            Some("θεοσ")
+        );
+        assert_eq!(Policy::new().contains_forbidden("pass"), None);
+        assert_eq!(
+            policy.validate("9godPASSword1234"),
+            Err(vec![Violation::ForbiddenSubstring(String::from("god"))])
+        );
+    }
+
+    #[test]
+    fn max_attempts_limits_candidates() {
+        let policy = unsatisfiable_policy().max_attempts(25);
+
+        assert_eq!(
+            generate_with_policy(&policy, &mut StdRng::seed_from_u64(42)),
+            Err(PolicyError::MaxAttemptsExceeded { attempts: 25 })
+        );
+        assert_eq!(
+            generate_with_policy(&policy.max_attempts(0), &mut StdRng::seed_from_u64(42)),
+            Err(PolicyError::MaxAttemptsExceeded { attempts: 0 })
+        );
+
+        let policy = Policy::new().max_consecutive_repeats(2).max_attempts(1);
+        assert!(generate_with_policy(&policy, &mut StdRng::seed_from_u64(42)).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_every_violation() {
+        let policy = Policy::new()
+            .min_length(10)
+            .max_length(20)
+            .require(ClassKind::Uppercase, 1)
+            .require(ClassKind::Digit, 2)
+            .exclude_chars("0O")
+            .max_consecutive_repeats(2)
+            .forbid_leading_trailing_whitespace(true);
+
+        assert_eq!(
+            policy.validate(" aaa0O-x"),
+            Err(vec![
+                Violation::TooShort { min: 10, actual: 8 },
+                Violation::MissingClass(ClassKind::Digit),
+                Violation::ForbiddenChar('0'),
+                Violation::ForbiddenChar('O'),
+                Violation::TooManyRepeats { max: 2, actual: 3 },
+                Violation::LeadingOrTrailingWhitespace,
+            ])
+        );
+        assert_eq!(
+            policy.validate("Abcdefghij12klmnopqrstu"),
+            Err(vec![Violation::TooLong {
+                max: 20,
+                actual: 23
+            }])
+        );
+        assert_eq!(policy.validate("Abcdefgh12"), Ok(()));
+    }
+
+    #[test]
+    fn validate_counts_chars_not_bytes() {
+        let policy = Policy::new().min_length(4).max_length(4);
+
+        assert_eq!(policy.validate("éàüö"), Ok(()));
+        assert_eq!(
+            policy.validate("éàü"),
+            Err(vec![Violation::TooShort { min: 4, actual: 3 }])
+        );
+    }
+
+    #[test]
+    fn generated_passwords_validate() {
+        let policy = Policy::new()
+            .min_length(8)
+            .max_length(12)
+            .require(ClassKind::Lowercase, 2)
+            .require(ClassKind::Symbol, 2)
+            .max_consecutive_repeats(1);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..500 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert_eq!(policy.validate(&password), Ok(()));
+        }
+    }
+
+    #[test]
+    fn nist_800_63b_validates() {
+        let policy = Policy::nist_800_63b();
+
+        assert_eq!(
+            policy.validate("hunter2"),
+            Err(vec![Violation::TooShort { min: 8, actual: 7 }])
+        );
+        assert_eq!(policy.validate("hunter22"), Ok(()));
+        assert_eq!(policy.validate(&"correct horse ".repeat(5)[..64]), Ok(()));
+        assert_eq!(
+            policy.validate(&"a".repeat(65)),
+            Err(vec![Violation::TooLong {
+                max: 64,
+                actual: 65
+            }])
+        );
+    }
+
+    #[test]
+    fn nist_800_63b_generates_for_entropy() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let policy = Policy::nist_800_63b();
+
+        assert_eq!(generate_with_policy(&policy, &mut rng).unwrap().len(), 20);
+
+        let policy = policy.target_entropy(40);
+        assert_eq!(generate_with_policy(&policy, &mut rng).unwrap().len(), 8);
+
+        let policy = policy.target_entropy(1000);
+        assert_eq!(generate_with_policy(&policy, &mut rng).unwrap().len(), 64);
+    }
+
+    #[test]
+    fn blocklist_ignores_case() {
+        let policy = Policy::nist_800_63b()
+            .blocklist(["Password1"])
+            .blocklist(vec![String::from("qwertyuiop")]);
+
+        assert_eq!(
+            policy.validate("PASSWORD1"),
+            Err(vec![Violation::Blocklisted])
+        );
+        assert_eq!(
+            policy.validate("QwertyUiop"),
+            Err(vec![Violation::Blocklisted])
+        );
+        assert_eq!(policy.validate("qwertyuiop1"), Ok(()));
+    }
+
+    #[test]
+    fn pci_dss_v4_validates() {
+        let policy = Policy::pci_dss_v4();
+
+        assert_eq!(policy.validate("abcdefghijk1"), Ok(()));
+        assert_eq!(policy.validate("ABCDEFGHIJK1"), Ok(()));
+        assert_eq!(
+            policy.validate("1234567890123"),
+            Err(vec![Violation::TooFewClasses {
+                required: 1,
+                actual: 0
+            }])
+        );
+        assert_eq!(
+            policy.validate("abcdefghij1"),
+            Err(vec![Violation::TooShort {
+                min: 12,
+                actual: 11
+            }])
+        );
+    }
+
+    #[test]
+    fn active_directory_rejects_account_name() {
+        let policy = Policy::active_directory(Some("jsmith"));
+
+        assert_eq!(policy.validate("Tr0ub4dor&3"), Ok(()));
+        assert_eq!(
+            policy.validate("xx-JSmith-99"),
+            Err(vec![Violation::ContainsAccountName])
+        );
+        assert_eq!(
+            policy.validate("jsmith"),
+            Err(vec![
+                Violation::TooShort { min: 7, actual: 6 },
+                Violation::TooFewClasses {
+                    required: 3,
+                    actual: 1
+                },
+                Violation::ContainsAccountName,
+            ])
+        );
+    }
+
+    #[test]
+    fn forbid_account_name_uses_tokens() {
+        let policy = Policy::active_directory(None)
+            .forbid_account_name("Jo Smith-Doe")
+            .forbid_account_name("ab");
+
+        assert_eq!(
+            policy.account_tokens.iter().collect::<Vec<_>>(),
+            ["doe", "jo smith-doe", "smith"]
+        );
+        assert_eq!(
+            policy.validate("Doe!2024x"),
+            Err(vec![Violation::ContainsAccountName])
+        );
+        assert_eq!(policy.validate("Jo!2024ab"), Ok(()));
+    }
+
+    #[test]
+    fn generate_with_policy_requires_classes() {
+        let policy = Policy::new()
+            .min_length(3)
+            .max_length(3)
+            .require(ClassKind::Digit, 1)
+            .require_classes(3, &ClassKind::ALL);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..500 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert_eq!(policy.validate(&password), Ok(()));
+            assert!(password.chars().any(|ch| ch.is_ascii_digit()));
+        }
+
+        let policy = Policy::active_directory(Some("administrator"));
+        for _ in 0..100 {
+            let password = generate_with_policy(&policy, &mut rng).unwrap();
+
+            assert_eq!(password.len(), 20);
+            assert_eq!(policy.validate(&password), Ok(()));
+        }
+    }
+
+    #[test]
+    fn require_classes_errors() {
+        let policy = Policy::new()
+            .pool(&Pool::digits())
+            .require_classes(2, &[ClassKind::Digit, ClassKind::Symbol, ClassKind::Digit]);
+
+        assert_eq!(
+            policy.build(),
+            Err(PolicyError::TooFewClasses {
+                required: 2,
+                available: 1
+            })
+        );
+
+        let policy = Policy::new()
+            .min_length(2)
+            .max_length(2)
+            .require(ClassKind::Digit, 1)
+            .require_classes(3, &ClassKind::ALL);
+
+        assert_eq!(
+            policy.build(),
+            Err(PolicyError::RequirementsTooLong {
+                required: 3,
+                max_length: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parse_policy() {
+        let policy: Policy = "len=12,digit>=2,repeat<=2,entropy=96,exclude=a\\,b\\\\"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            policy,
+            Policy::new()
+                .min_length(12)
+                .max_length(12)
+                .require(ClassKind::Digit, 2)
+                .max_consecutive_repeats(2)
+                .target_entropy(96)
+                .exclude_chars("a,b\\")
+        );
+        assert_eq!("".parse(), Ok(Policy::new()));
+        assert_eq!(
+            "len=8,classes>=3:lower+upper+digit,no-edge-space=1,block=a\\|b|c,account=j.doe"
+                .parse(),
+            Ok(Policy::new()
+                .min_length(8)
+                .max_length(8)
+                .require_classes(
+                    3,
+                    &[ClassKind::Lowercase, ClassKind::Uppercase, ClassKind::Digit]
+                )
+                .forbid_leading_trailing_whitespace(true)
+                .blocklist(["a|b", "c"])
+                .forbid_account_name("j.doe"))
+        );
+    }
+
+    #[test]
+    fn parse_policy_errors() {
+        let cases = [
+            (
+                "len=8,size=4",
+                PolicyParseError::UnknownKey {
+                    token: "size=4".into(),
+                    position: 6,
+                },
+            ),
+            (
+                "len=8,,digit>=1",
+                PolicyParseError::Malformed {
+                    token: "".into(),
+                    position: 6,
+                },
+            ),
+            (
+                "digit=1",
+                PolicyParseError::Malformed {
+                    token: "digit=1".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "len=8..x",
+                PolicyParseError::InvalidValue {
+                    token: "len=8..x".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "exclude=ab\\",
+                PolicyParseError::InvalidValue {
+                    token: "exclude=ab\\".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "classes>=2:lower+green",
+                PolicyParseError::InvalidValue {
+                    token: "classes>=2:lower+green".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "no-edge-space=yes",
+                PolicyParseError::InvalidValue {
+                    token: "no-edge-space=yes".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "len=8,no-keyboard-walk=2",
+                PolicyParseError::InvalidValue {
+                    token: "no-keyboard-walk=2".into(),
+                    position: 6,
+                },
+            ),
+            (
+                "digit>=1,len=8,digit>=2",
+                PolicyParseError::DuplicateKey {
+                    token: "digit>=2".into(),
+                    position: 15,
+                },
+            ),
+        ];
+
+        for (s, error) in cases {
+            assert_eq!(s.parse::<Policy>(), Err(error));
+        }
+    }
+
+    #[test]
+    fn parse_policy_conflicting_constraints() {
+        assert_eq!(
+            "len=32..16".parse::<Policy>(),
+            Err(PolicyParseError::Invalid(PolicyError::InvalidLengthRange {
+                min: 32,
+                max: 16
+            }))
+        );
+        assert_eq!(
+            "len=4,digit>=3,symbol>=2".parse::<Policy>(),
+            Err(PolicyParseError::Invalid(
+                PolicyError::RequirementsTooLong {
+                    required: 5,
+                    max_length: 4
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn policy_display_round_trips() {
+        let policies = [
+            Policy::new(),
+            Policy::new()
+                .min_length(8)
+                .max_length(64)
+                .require(ClassKind::Symbol, 1)
+                .require(ClassKind::Lowercase, 0),
+            Policy::nist_800_63b().max_consecutive_repeats(3),
+            Policy::new().max_attempts(10),
+            Policy::new().forbid_sequences(3),
+            Policy::new()
+                .max_class_run(ClassKind::Digit, 3)
+                .max_class_run(ClassKind::Symbol, 1),
+            Policy::new().exclude_chars("\\,=>,l1"),
+            Policy::new().forbid_leading_trailing_whitespace(true),
+            Policy::new().avoid_keyboard_walks(true),
+            Policy::new().require_classes(2, &[ClassKind::Digit, ClassKind::Symbol]),
+            Policy::new().require_classes(0, &[]),
+            Policy::new().blocklist(["pass,word", "a|b\\c", ""]),
+            Policy::new().forbid_substrings(["god", "Σ|x"]),
+            Policy::new().pool(&"ab,|\\=".parse().unwrap()),
+            Policy::pci_dss_v4(),
+            Policy::active_directory(Some("John.Doe-Smith")),
+        ];
+
+        for policy in policies {
+            assert_eq!(policy.to_string().parse(), Ok(policy));
+        }
+        assert_eq!(
+            "len=16..32,lower>=1,upper>=1,digit>=2,symbol>=1,exclude=l1IO0"
+                .parse::<Policy>()
+                .unwrap()
+                .to_string(),
+            "len=16..32,lower>=1,upper>=1,digit>=2,symbol>=1,exclude=l1IO0"
+        );
+    }
+
+    #[test]
+    fn length_for_entropy_exact() {
+        assert_eq!(length_for_entropy(128, 94, 64), 20);
+        assert_eq!(length_for_entropy(128, 64, 64), 22);
+        assert_eq!(length_for_entropy(96, 64, 64), 16);
+        assert_eq!(length_for_entropy(97, 64, 64), 17);
+        assert_eq!(length_for_entropy(0, 10, 64), 1);
+        assert_eq!(length_for_entropy(64, 2, 32), 32);
+        assert!((2..200).all(|size| {
+            let length = length_for_entropy(100, size, 1000) as f64;
+            let entropy = (size as f64).log2();
+
+            length * entropy >= 100.0 && (length - 1.0) * entropy < 100.0
+        }));
+    }
+
+    #[test]
+    fn longest_run_counts_chars() {
+        assert_eq!(longest_run(""), 0);
+        assert_eq!(longest_run("abc"), 1);
+        assert_eq!(longest_run("aabbbc"), 3);
+        assert_eq!(longest_run("ééé"), 3);
+    }
+}
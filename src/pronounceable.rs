@@ -0,0 +1,136 @@
+use crate::requirements::pick;
+use crate::{PassgenError, Pool};
+
+/// Consonants used by [`generate_pronounceable`]. `c`, `q`, `w`, `x` and `y` are left out
+/// because their pronunciation is ambiguous.
+pub const CONSONANTS: &str = "bdfghjklmnprstvz";
+
+/// Vowels used by [`generate_pronounceable`]
+pub const VOWELS: &str = "aeiou";
+
+/// Generate pronounceable lowercase password made of `syllable_count` consonant-vowel
+/// syllables, like `tazobime`, using [`CONSONANTS`] and [`VOWELS`].
+///
+/// Pronounceable passwords are much weaker than random ones of the same length: each
+/// syllable only adds `log2(16 * 5)`, about 6.3 bits, against 9.4 bits for 2 random
+/// lowercase letters. See [`pronounceable_entropy`] to choose the number of syllables.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::generate_pronounceable;
+/// let password = generate_pronounceable(5);
+///
+/// assert_eq!(password.len(), 10);
+/// ```
+///
+/// # Panics
+/// Panics if `syllable_count` is zero.
+pub fn generate_pronounceable(syllable_count: usize) -> String {
+    let consonants: Pool = CONSONANTS.parse().unwrap();
+    let vowels: Pool = VOWELS.parse().unwrap();
+
+    generate_pronounceable_with_pools(&consonants, &vowels, syllable_count)
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Generate pronounceable password made of `syllable_count` syllables, each one a char of
+/// `consonants` followed by a char of `vowels`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_pronounceable_with_pools};
+/// let consonants: Pool = "BDKLMNPRST".parse().unwrap();
+/// let vowels: Pool = "AEIOU".parse().unwrap();
+/// let password = generate_pronounceable_with_pools(&consonants, &vowels, 4).unwrap();
+///
+/// assert_eq!(password.len(), 8);
+/// assert!(password.chars().all(|ch| ch.is_ascii_uppercase()));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `consonants` or `vowels` is empty and
+/// [`PassgenError::ZeroLength`] if `syllable_count` is zero.
+pub fn generate_pronounceable_with_pools(
+    consonants: &Pool,
+    vowels: &Pool,
+    syllable_count: usize,
+) -> Result<String, PassgenError> {
+    if consonants.is_empty() || vowels.is_empty() {
+        return Err(PassgenError::EmptyPool);
+    }
+    if syllable_count == 0 {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    let mut rng = rand::thread_rng();
+
+    Ok((0..syllable_count)
+        .flat_map(|_| [pick(consonants, &mut rng), pick(vowels, &mut rng)])
+        .collect())
+}
+
+/// Calculates entropy of a pronounceable password of `syllable_count` syllables, drawn from
+/// `consonant_count` consonants and `vowel_count` vowels.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{pronounceable_entropy, CONSONANTS, VOWELS};
+/// let entropy = pronounceable_entropy(10, CONSONANTS.len(), VOWELS.len());
+///
+/// assert!((entropy - 63.2).abs() < 0.1);
+/// ```
+pub fn pronounceable_entropy(
+    syllable_count: usize,
+    consonant_count: usize,
+    vowel_count: usize,
+) -> f64 {
+    syllable_count as f64 * ((consonant_count * vowel_count) as f64).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_pronounceable_alternates() {
+        for _ in 0..100 {
+            let password = generate_pronounceable(6);
+
+            assert_eq!(password.len(), 12);
+            for (idx, ch) in password.chars().enumerate() {
+                if idx % 2 == 0 {
+                    assert!(CONSONANTS.contains(ch));
+                } else {
+                    assert!(VOWELS.contains(ch));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Password length must be greater than zero!")]
+    fn generate_pronounceable_passed_zero() {
+        generate_pronounceable(0);
+    }
+
+    #[test]
+    fn generate_pronounceable_with_pools_custom() {
+        let consonants: Pool = "k".parse().unwrap();
+        let vowels: Pool = "a".parse().unwrap();
+
+        assert_eq!(
+            generate_pronounceable_with_pools(&consonants, &vowels, 3),
+            Ok("kakaka".to_owned())
+        );
+        assert_eq!(
+            generate_pronounceable_with_pools(&Pool::new(), &vowels, 3),
+            Err(PassgenError::EmptyPool)
+        );
+    }
+
+    #[test]
+    fn pronounceable_entropy_matches_pools() {
+        assert_eq!(pronounceable_entropy(3, 16, 4), 18_f64);
+        assert_eq!(pronounceable_entropy(0, 16, 4), 0_f64);
+    }
+}
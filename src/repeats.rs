@@ -0,0 +1,254 @@
+use crate::{unwrap_or_empty, validate, PassgenError, Pool};
+use alloc::string::String;
+use rand::Rng;
+
+/// Generate random password in which no char is immediately followed by the same char, so
+/// `"aabb"` style repeats never occur.
+///
+/// The first char is drawn from the whole pool and every following one from the other
+/// `pool.len() - 1` chars. This reduces the entropy to
+/// `log2(n) + (length - 1) * log2(n - 1)` bits for a pool of `n` chars, instead of
+/// `length * log2(n)`. The loss is about 5% of the bits for [`Pool::digits`] and below 1%
+/// for [`Pool::alphanumeric`], but a 2 chars pool only gives 1 bit whatever the length.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_no_repeats};
+/// let password = generate_password_no_repeats(&Pool::digits(), 20);
+/// let chars: Vec<char> = password.chars().collect();
+///
+/// assert!(chars.windows(2).all(|w| w[0] != w[1]));
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` has fewer than 2 chars and `length` is not zero. See
+/// [`try_generate_password_no_repeats`] for a non-panicking version, which rejects a zero
+/// `length`.
+#[cfg(feature = "std")]
+pub fn generate_password_no_repeats(pool: &Pool, length: usize) -> String {
+    unwrap_or_empty(try_generate_password_no_repeats(pool, length))
+}
+
+/// Generate random password without consecutive repeated chars, returning an error instead
+/// of panicking.
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero and [`PassgenError::PoolTooSmall`] if `pool` has a single char.
+#[cfg(feature = "std")]
+pub fn try_generate_password_no_repeats(
+    pool: &Pool,
+    length: usize,
+) -> Result<String, PassgenError> {
+    validate_no_repeats(pool, length)?;
+
+    Ok(sample_no_repeats(pool, length, &mut rand::thread_rng()))
+}
+
+/// Generate random password without consecutive repeated chars using the given random number
+/// generator. See [`generate_password_no_repeats`](crate::generate_password_no_repeats).
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` has fewer than 2 chars and `length` is not zero.
+pub fn generate_password_no_repeats_with_rng<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    rng: &mut R,
+) -> String {
+    unwrap_or_empty(
+        validate_no_repeats(pool, length).map(|()| sample_no_repeats(pool, length, rng)),
+    )
+}
+
+/// Check that a password without consecutive repeats can be drawn from `pool`
+pub(crate) fn validate_no_repeats(pool: &Pool, length: usize) -> Result<(), PassgenError> {
+    validate(pool, length)?;
+    if pool.len() < 2 {
+        return Err(PassgenError::PoolTooSmall {
+            len: pool.len(),
+            required: 2,
+        });
+    }
+
+    Ok(())
+}
+
+/// Draw `length` chars, each one uniformly among the chars of `pool` other than the previous
+/// one. The pool must have at least 2 chars.
+pub(crate) fn sample_no_repeats<R: Rng + ?Sized>(
+    pool: &Pool,
+    length: usize,
+    rng: &mut R,
+) -> String {
+    let mut password = String::with_capacity(crate::max_char_len(pool) * length);
+    let mut previous = rng.gen_range(0..pool.len());
+    password.push(*pool.get(previous).unwrap());
+
+    for _ in 1..length {
+        // Skip over the previous index so each other char stays equally likely
+        let mut idx = rng.gen_range(0..pool.len() - 1);
+        if idx >= previous {
+            idx += 1;
+        }
+        password.push(*pool.get(idx).unwrap());
+        previous = idx;
+    }
+
+    password
+}
+
+/// Generate random password in which every char is distinct, sampling `pool` without
+/// replacement.
+///
+/// Each of the `n! / (n - length)!` arrangements of `length` distinct chars of a pool of `n`
+/// chars is equally likely, so the entropy is `log2(n! / (n - length)!)` bits and not
+/// [`calculate_entropy`](crate::calculate_entropy)`(length, n)`. With `length == n` the
+/// password is a random permutation of the pool.
+///
+/// This is [`generate_distinct_password`](crate::generate_distinct_password) with
+/// [`rand::thread_rng`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, PassgenError, generate_unique_password};
+/// let password = generate_unique_password(&Pool::digits(), 10).unwrap();
+/// let mut chars: Vec<char> = password.chars().collect();
+/// chars.sort();
+///
+/// assert_eq!(chars.into_iter().collect::<String>(), "0123456789");
+/// assert!(generate_unique_password(&Pool::digits(), 11).is_err());
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero and [`PassgenError::PoolTooSmall`] if `length` is greater than the number
+/// of chars of `pool`.
+#[cfg(feature = "std")]
+pub fn generate_unique_password(pool: &Pool, length: usize) -> Result<String, PassgenError> {
+    crate::generate_distinct_password(pool, length, &mut rand::thread_rng())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generate_password_no_repeats_never_repeats() {
+        let pool: Pool = "ab".parse().unwrap();
+
+        for _ in 0..1_000 {
+            let password = generate_password_no_repeats(&pool, 32);
+            let chars: Vec<char> = password.chars().collect();
+
+            assert_eq!(chars.len(), 32);
+            assert!(chars.windows(2).all(|w| w[0] != w[1]));
+        }
+    }
+
+    #[test]
+    fn generate_password_no_repeats_uses_whole_pool() {
+        let pool = Pool::digits();
+        let password = generate_password_no_repeats(&pool, 2_000);
+
+        assert!(pool.iter().all(|ch| password.contains(*ch)));
+    }
+
+    #[test]
+    fn generate_password_no_repeats_length_one() {
+        let pool: Pool = "ab".parse().unwrap();
+
+        assert_eq!(generate_password_no_repeats(&pool, 1).len(), 1);
+    }
+
+    #[test]
+    fn generate_password_no_repeats_zero_length() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(generate_password_no_repeats(&Pool::digits(), 0), "");
+        assert_eq!(
+            generate_password_no_repeats_with_rng(&"a".parse().unwrap(), 0, &mut rng),
+            ""
+        );
+    }
+
+    #[test]
+    fn generate_password_no_repeats_with_rng_same_seed() {
+        let pool = Pool::alphanumeric();
+
+        assert_eq!(
+            generate_password_no_repeats_with_rng(&pool, 16, &mut StdRng::seed_from_u64(42)),
+            generate_password_no_repeats_with_rng(&pool, 16, &mut StdRng::seed_from_u64(42))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains 1 chars, at least 2 are required!")]
+    fn generate_password_no_repeats_passed_single_char_pool() {
+        generate_password_no_repeats(&"a".parse().unwrap(), 5);
+    }
+
+    #[test]
+    fn generate_unique_password_distinct_chars() {
+        let pool = Pool::alphanumeric();
+
+        for _ in 0..1_000 {
+            let password = generate_unique_password(&pool, 20).unwrap();
+            let distinct: Pool = password.chars().collect();
+
+            assert_eq!(password.chars().count(), 20);
+            assert_eq!(distinct.len(), 20);
+        }
+    }
+
+    #[test]
+    fn generate_unique_password_whole_pool_is_permutation() {
+        let pool: Pool = "abcdef".parse().unwrap();
+        let password = generate_unique_password(&pool, 6).unwrap();
+
+        assert_eq!(password.chars().collect::<Pool>(), pool);
+    }
+
+    #[test]
+    fn generate_unique_password_errors() {
+        assert_eq!(
+            generate_unique_password(&Pool::digits(), 11),
+            Err(PassgenError::PoolTooSmall {
+                len: 10,
+                required: 11
+            })
+        );
+        assert_eq!(
+            generate_unique_password(&Pool::new(), 1),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generate_unique_password(&Pool::digits(), 0),
+            Err(PassgenError::ZeroLength)
+        );
+    }
+
+    #[test]
+    fn try_generate_password_no_repeats_errors() {
+        assert_eq!(
+            try_generate_password_no_repeats(&Pool::new(), 5),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            try_generate_password_no_repeats(&Pool::digits(), 0),
+            Err(PassgenError::ZeroLength)
+        );
+        assert_eq!(
+            try_generate_password_no_repeats(&"a".parse().unwrap(), 5),
+            Err(PassgenError::PoolTooSmall {
+                len: 1,
+                required: 2
+            })
+        );
+    }
+}
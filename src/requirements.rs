@@ -0,0 +1,379 @@
+use crate::Pool;
+#[cfg(feature = "std")]
+use crate::{validate, PassgenError};
+use alloc::string::String;
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Generate random password containing at least one char of each pool of `requirements`.
+///
+/// One char is drawn from each requirement, the remaining positions are drawn from `pool`,
+/// then all positions are shuffled. Every password satisfying the requirements can be
+/// produced, but the distribution is not uniform over them: chars of the requirement pools
+/// are slightly more frequent than with [`generate_password`](crate::generate_password).
+/// The requirement pools do not have to be subsets of `pool`.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_with_requirements};
+/// let requirements = [Pool::uppercase(), Pool::digits(), Pool::ascii_symbols()];
+/// let password = generate_password_with_requirements(&Pool::lowercase(), 12, &requirements);
+///
+/// assert_eq!(password.chars().count(), 12);
+/// assert!(password.chars().any(|ch| ch.is_ascii_digit()));
+/// ```
+///
+/// An empty password is returned when `length` is zero and there are no requirements.
+///
+/// # Panics
+/// Panics if one of the pools is empty or if `length` is smaller than the number of
+/// requirements, which includes a zero `length` with requirements. See
+/// [`try_generate_password_with_requirements`] for a non-panicking version.
+#[cfg(feature = "std")]
+pub fn generate_password_with_requirements(
+    pool: &Pool,
+    length: usize,
+    requirements: &[Pool],
+) -> String {
+    match try_generate_password_with_requirements(pool, length, requirements) {
+        Err(PassgenError::ZeroLength) if requirements.is_empty() => String::new(),
+        result => result.unwrap_or_else(|e| panic!("{}", e)),
+    }
+}
+
+/// Generate random password containing at least one char of each pool of `requirements`,
+/// returning an error instead of panicking.
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` or one of the requirements is empty,
+/// [`PassgenError::ZeroLength`] if `length` is zero and [`PassgenError::LengthTooShort`] if
+/// `length` is smaller than the number of requirements.
+#[cfg(feature = "std")]
+pub fn try_generate_password_with_requirements(
+    pool: &Pool,
+    length: usize,
+    requirements: &[Pool],
+) -> Result<String, PassgenError> {
+    validate(pool, length)?;
+    if requirements.iter().any(Pool::is_empty) {
+        return Err(PassgenError::EmptyPool);
+    }
+    if length < requirements.len() {
+        return Err(PassgenError::LengthTooShort {
+            length,
+            required: requirements.len(),
+        });
+    }
+
+    let required: Vec<(&Pool, usize)> = requirements.iter().map(|pool| (pool, 1)).collect();
+
+    Ok(place_and_shuffle(
+        &required,
+        pool,
+        length,
+        &mut rand::thread_rng(),
+    ))
+}
+
+/// Generate random password of chars picked from the union of `pools`, containing at least
+/// one char of each pool, like the classic "one lowercase, one uppercase, one digit and one
+/// symbol" policy.
+///
+/// This is [`try_generate_password_with_requirements`] with the union of `pools` as pool: one
+/// char is drawn from each pool and inserted at a random position, never by rejecting
+/// passwords, so it always terminates. The same bias applies: chars of small pools are
+/// slightly more frequent than in a uniform draw from the union.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_with_all};
+/// let pools = [Pool::lowercase(), Pool::uppercase(), Pool::digits(), Pool::ascii_symbols()];
+/// let password = generate_password_with_all(&pools, 12).unwrap();
+///
+/// assert_eq!(password.chars().count(), 12);
+/// assert!(password.chars().any(|ch| ch.is_ascii_punctuation()));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pools` or one of its pools is empty,
+/// [`PassgenError::ZeroLength`] if `length` is zero and [`PassgenError::LengthTooShort`] if
+/// `length` is smaller than the number of pools.
+#[cfg(feature = "std")]
+pub fn generate_password_with_all(pools: &[Pool], length: usize) -> Result<String, PassgenError> {
+    let union = pools
+        .iter()
+        .fold(Pool::new(), |union, pool| union.union(pool));
+
+    try_generate_password_with_requirements(&union, length, pools)
+}
+
+/// Generate random password of `total_length` chars with exactly `count` chars of each
+/// `(pool, count)` of `spec`, the remaining positions being filled from `filler`, like "2
+/// digits, 1 symbol and letters for the rest".
+///
+/// The chars are drawn from their pools, then the positions are shuffled uniformly with a
+/// Fisher-Yates shuffle. The counts are only exact if `filler` and the pools of `spec` have
+/// no char in common, otherwise filler chars add to them.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_password_with_counts};
+/// let spec = [(Pool::digits(), 2), (Pool::ascii_symbols(), 1)];
+/// let password = generate_password_with_counts(&spec, &Pool::lowercase(), 10).unwrap();
+///
+/// assert_eq!(password.chars().filter(|ch| ch.is_ascii_digit()).count(), 2);
+/// assert_eq!(password.chars().filter(|ch| ch.is_ascii_punctuation()).count(), 1);
+/// assert_eq!(password.chars().filter(|ch| ch.is_ascii_lowercase()).count(), 7);
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `filler` or a pool of `spec` is empty,
+/// [`PassgenError::ZeroLength`] if `total_length` is zero and
+/// [`PassgenError::LengthTooShort`] if the counts add up to more than `total_length`.
+#[cfg(feature = "std")]
+pub fn generate_password_with_counts(
+    spec: &[(Pool, usize)],
+    filler: &Pool,
+    total_length: usize,
+) -> Result<String, PassgenError> {
+    validate(filler, total_length)?;
+    if spec.iter().any(|(pool, _)| pool.is_empty()) {
+        return Err(PassgenError::EmptyPool);
+    }
+    let required = spec
+        .iter()
+        .fold(0usize, |sum, (_, count)| sum.saturating_add(*count));
+    if total_length < required {
+        return Err(PassgenError::LengthTooShort {
+            length: total_length,
+            required,
+        });
+    }
+
+    let spec: Vec<(&Pool, usize)> = spec.iter().map(|(pool, count)| (pool, *count)).collect();
+
+    Ok(place_and_shuffle(
+        &spec,
+        filler,
+        total_length,
+        &mut rand::thread_rng(),
+    ))
+}
+
+/// Draw `count` chars from each pool of `required`, fill up to `length` chars from `filler`
+/// and shuffle the result. The counts must not exceed `length` and the pools must not be
+/// empty.
+pub(crate) fn place_and_shuffle<R: Rng + ?Sized>(
+    required: &[(&Pool, usize)],
+    filler: &Pool,
+    length: usize,
+    rng: &mut R,
+) -> String {
+    let mut chars: Vec<char> = Vec::with_capacity(length);
+    for (pool, count) in required {
+        chars.extend((0..*count).map(|_| pick(pool, rng)));
+    }
+    while chars.len() < length {
+        chars.push(pick(filler, rng));
+    }
+    chars.shuffle(rng);
+
+    chars.into_iter().collect()
+}
+
+pub(crate) fn pick<R: Rng + ?Sized>(pool: &Pool, rng: &mut R) -> char {
+    *pool.get(rng.gen_range(0..pool.len())).unwrap()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_password_with_requirements_assert_len() {
+        let password = generate_password_with_requirements(&Pool::lowercase(), 16, &[]);
+
+        assert_eq!(password.chars().count(), 16);
+    }
+
+    #[test]
+    fn generate_password_with_requirements_always_satisfied() {
+        let requirements = [Pool::uppercase(), Pool::digits(), Pool::ascii_symbols()];
+
+        for _ in 0..1000 {
+            let password =
+                generate_password_with_requirements(&Pool::lowercase(), 4, &requirements);
+
+            assert!(password.chars().any(|ch| ch.is_ascii_uppercase()));
+            assert!(password.chars().any(|ch| ch.is_ascii_digit()));
+            assert!(password.chars().any(|ch| ch.is_ascii_punctuation()));
+            assert!(password.chars().any(|ch| ch.is_ascii_lowercase()));
+        }
+    }
+
+    #[test]
+    fn generate_password_with_requirements_length_equals_requirements() {
+        let requirements = ["a".parse().unwrap(), "b".parse().unwrap()];
+        let password = generate_password_with_requirements(&Pool::digits(), 2, &requirements);
+
+        assert!(password == "ab" || password == "ba");
+    }
+
+    #[test]
+    fn generate_password_with_requirements_positions_vary() {
+        let requirements = ["X".parse().unwrap()];
+        let positions: Vec<usize> = (0..200)
+            .map(|_| {
+                generate_password_with_requirements(&"a".parse().unwrap(), 4, &requirements)
+                    .find('X')
+                    .unwrap()
+            })
+            .collect();
+
+        assert!((0..4).all(|idx| positions.contains(&idx)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Password length 2 is shorter than the 3 required chars!")]
+    fn generate_password_with_requirements_length_too_short() {
+        let requirements = [Pool::uppercase(), Pool::digits(), Pool::ascii_symbols()];
+
+        generate_password_with_requirements(&Pool::lowercase(), 2, &requirements);
+    }
+
+    #[test]
+    fn generate_password_with_requirements_zero_length() {
+        assert_eq!(
+            generate_password_with_requirements(&Pool::lowercase(), 0, &[]),
+            ""
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Password length must be greater than zero!")]
+    fn generate_password_with_requirements_zero_length_with_requirements() {
+        generate_password_with_requirements(&Pool::lowercase(), 0, &[Pool::digits()]);
+    }
+
+    #[test]
+    fn try_generate_password_with_requirements_length_too_short() {
+        let requirements = [Pool::uppercase(), Pool::digits()];
+        let result = try_generate_password_with_requirements(&Pool::lowercase(), 1, &requirements);
+
+        assert_eq!(
+            result,
+            Err(PassgenError::LengthTooShort {
+                length: 1,
+                required: 2
+            })
+        );
+    }
+
+    #[test]
+    fn try_generate_password_with_requirements_empty_requirement() {
+        let result = try_generate_password_with_requirements(&Pool::lowercase(), 8, &[Pool::new()]);
+
+        assert_eq!(result, Err(PassgenError::EmptyPool));
+    }
+
+    #[test]
+    fn try_generate_password_with_requirements_empty_pool() {
+        let result = try_generate_password_with_requirements(&Pool::new(), 8, &[Pool::digits()]);
+
+        assert_eq!(result, Err(PassgenError::EmptyPool));
+    }
+
+    #[test]
+    fn generate_password_with_all_always_satisfied() {
+        let pools = [
+            Pool::lowercase(),
+            Pool::uppercase(),
+            Pool::digits(),
+            "!#".parse().unwrap(),
+        ];
+
+        for _ in 0..1000 {
+            let password = generate_password_with_all(&pools, 5).unwrap();
+
+            assert_eq!(password.chars().count(), 5);
+            assert!(pools
+                .iter()
+                .all(|pool| password.chars().any(|ch| pool.contains(ch))));
+        }
+    }
+
+    #[test]
+    fn generate_password_with_all_errors() {
+        let pools = [Pool::lowercase(), Pool::digits()];
+
+        assert_eq!(
+            generate_password_with_all(&pools, 1),
+            Err(PassgenError::LengthTooShort {
+                length: 1,
+                required: 2
+            })
+        );
+        assert_eq!(
+            generate_password_with_all(&[Pool::digits(), Pool::new()], 8),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generate_password_with_all(&[], 8),
+            Err(PassgenError::EmptyPool)
+        );
+    }
+
+    #[test]
+    fn generate_password_with_counts_exact() {
+        let spec = [
+            (Pool::digits(), 2),
+            (Pool::ascii_symbols(), 1),
+            (Pool::uppercase(), 0),
+        ];
+        let mut first_digit = [0; 6];
+
+        for _ in 0..1000 {
+            let password = generate_password_with_counts(&spec, &Pool::lowercase(), 6).unwrap();
+            let count = |class: fn(&char) -> bool| password.chars().filter(class).count();
+
+            assert_eq!(count(char::is_ascii_digit), 2);
+            assert_eq!(count(char::is_ascii_punctuation), 1);
+            assert_eq!(count(char::is_ascii_uppercase), 0);
+            assert_eq!(count(char::is_ascii_lowercase), 3);
+            first_digit[password.find(|ch: char| ch.is_ascii_digit()).unwrap()] += 1;
+        }
+        // The first digit can be anywhere but in the last position
+        assert!(first_digit[..5].iter().all(|count| *count > 0));
+    }
+
+    #[test]
+    fn generate_password_with_counts_no_filler_needed() {
+        let spec = [("a".parse().unwrap(), 2), ("b".parse().unwrap(), 1)];
+        let password = generate_password_with_counts(&spec, &"c".parse().unwrap(), 3).unwrap();
+
+        assert_eq!(password.matches('a').count(), 2);
+        assert_eq!(password.matches('b').count(), 1);
+    }
+
+    #[test]
+    fn generate_password_with_counts_errors() {
+        let spec = [(Pool::digits(), 3), (Pool::ascii_symbols(), 2)];
+
+        assert_eq!(
+            generate_password_with_counts(&spec, &Pool::lowercase(), 4),
+            Err(PassgenError::LengthTooShort {
+                length: 4,
+                required: 5
+            })
+        );
+        assert_eq!(
+            generate_password_with_counts(&[(Pool::new(), 1)], &Pool::lowercase(), 4),
+            Err(PassgenError::EmptyPool)
+        );
+        assert_eq!(
+            generate_password_with_counts(&spec, &Pool::new(), 8),
+            Err(PassgenError::EmptyPool)
+        );
+    }
+}
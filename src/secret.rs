@@ -0,0 +1,108 @@
+#[cfg(feature = "std")]
+use crate::{password_iter_with_rng, sample_password, unwrap_or_empty, validate, Pool};
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use secrecy::SecretString;
+use zeroize::Zeroize;
+
+/// Generate random password as a [`SecretString`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_secret};
+/// use secrecy::ExposeSecret;
+///
+/// let pool = "0123456789".parse().unwrap();
+/// let secret = generate_secret(&pool, 15);
+///
+/// assert_eq!(secret.expose_secret().len(), 15);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_secret(pool: &Pool, length: usize) -> SecretString {
+    unwrap_or_empty(validate(pool, length));
+
+    into_secret(sample_password(pool, length, &mut rand::thread_rng()))
+}
+
+/// Generate multiple random passwords as [`SecretString`]s.
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_n_secrets(pool: &Pool, length: usize, count: usize) -> Vec<SecretString> {
+    password_iter_with_rng(pool, length, rand::thread_rng())
+        .take(count)
+        .map(into_secret)
+        .collect()
+}
+
+/// Move `password` into a [`SecretString`] without leaving an unwiped copy behind.
+///
+/// Converting a `String` whose capacity is larger than its length would reallocate it and
+/// free the old buffer as is, so in that case the password is copied and the original wiped.
+pub(crate) fn into_secret(mut password: String) -> SecretString {
+    if password.len() == password.capacity() {
+        return SecretString::from(password);
+    }
+
+    let secret = SecretString::from(password.as_str());
+    password.zeroize();
+
+    secret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::ExposeSecret;
+
+    #[test]
+    fn generate_secret_assert_len() {
+        let secret = generate_secret(&Pool::digits(), 15);
+
+        assert_eq!(secret.expose_secret().len(), 15);
+        assert!(secret.expose_secret().chars().all(|ch| ch.is_ascii_digit()));
+    }
+
+    #[test]
+    fn generate_secret_multibyte_pool() {
+        let secret = generate_secret(&"aé€".parse().unwrap(), 15);
+
+        assert_eq!(secret.expose_secret().chars().count(), 15);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_secret_passed_empty_pool() {
+        generate_secret(&Pool::new(), 15);
+    }
+
+    #[test]
+    fn generate_secret_zero_length() {
+        assert_eq!(generate_secret(&Pool::digits(), 0).expose_secret(), "");
+    }
+
+    #[test]
+    fn generate_n_secrets_assert_count() {
+        let secrets = generate_n_secrets(&Pool::digits(), 15, 10);
+
+        assert_eq!(secrets.len(), 10);
+        assert!(secrets.iter().all(|s| s.expose_secret().len() == 15));
+    }
+
+    #[test]
+    fn into_secret_with_spare_capacity() {
+        let mut password = String::with_capacity(64);
+        password.push_str("hunter2");
+
+        assert_eq!(into_secret(password).expose_secret(), "hunter2");
+    }
+}
@@ -0,0 +1,184 @@
+#[cfg(feature = "std")]
+use crate::{password_iter_with_rng, sample_password, unwrap_or_empty, validate, Pool};
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Generated password which is wiped from memory when dropped.
+///
+/// The buffer is allocated once with its final capacity, so no partial copy of the password
+/// is left behind by a reallocation during generation.
+///
+/// The password can only be read through [`SecurePassword::expose`]: there is no `Deref`,
+/// `Display` or implicit conversion, and `Debug` prints `SecurePassword(***)`.
+pub struct SecurePassword(String);
+
+impl SecurePassword {
+    /// Returns the password
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecurePassword {
+    /// Take ownership of `password`. Copies of it made before the conversion are not wiped.
+    fn from(password: String) -> Self {
+        SecurePassword(password)
+    }
+}
+
+impl fmt::Debug for SecurePassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecurePassword(***)")
+    }
+}
+
+impl Zeroize for SecurePassword {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecurePassword {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecurePassword {}
+
+#[cfg(feature = "secrecy")]
+impl From<SecurePassword> for secrecy::SecretString {
+    fn from(mut password: SecurePassword) -> Self {
+        crate::secret::into_secret(core::mem::take(&mut password.0))
+    }
+}
+
+/// Generate random password which is wiped from memory when dropped.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_secure_password};
+/// let pool = "0123456789".parse().unwrap();
+/// let password = generate_secure_password(&pool, 15);
+///
+/// assert_eq!(password.expose().len(), 15);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_secure_password(pool: &Pool, length: usize) -> SecurePassword {
+    unwrap_or_empty(validate(pool, length));
+
+    SecurePassword(sample_password(pool, length, &mut rand::thread_rng()))
+}
+
+/// Generate multiple random passwords which are wiped from memory when dropped.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_n_secure_passwords};
+/// let pool = "0123456789".parse().unwrap();
+/// let passwords = generate_n_secure_passwords(&pool, 15, 5);
+///
+/// assert_eq!(passwords.len(), 5);
+/// ```
+///
+/// Empty passwords are returned when `length` is zero.
+///
+/// # Panics
+/// Panics if `pool` is empty.
+#[cfg(feature = "std")]
+pub fn generate_n_secure_passwords(
+    pool: &Pool,
+    length: usize,
+    count: usize,
+) -> Vec<SecurePassword> {
+    password_iter_with_rng(pool, length, rand::thread_rng())
+        .take(count)
+        .map(SecurePassword)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_secure_password_assert_len() {
+        let password = generate_secure_password(&Pool::digits(), 15);
+
+        assert_eq!(password.expose().chars().count(), 15);
+        assert!(password.expose().chars().all(|ch| ch.is_ascii_digit()));
+    }
+
+    #[test]
+    fn generate_secure_password_no_reallocation() {
+        let password = generate_secure_password(&"é€".parse().unwrap(), 10);
+
+        assert_eq!(password.0.capacity(), 30);
+    }
+
+    #[test]
+    fn secure_password_zeroize() {
+        let mut password = generate_secure_password(&Pool::digits(), 15);
+        password.zeroize();
+
+        assert!(password.expose().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_secure_password_passed_empty_pool() {
+        generate_secure_password(&Pool::new(), 15);
+    }
+
+    #[test]
+    fn generate_secure_password_zero_length() {
+        assert_eq!(generate_secure_password(&Pool::digits(), 0).expose(), "");
+    }
+
+    #[test]
+    fn secure_password_debug_is_redacted() {
+        let password = generate_secure_password(&"x".parse().unwrap(), 15);
+
+        assert_eq!(format!("{:?}", password), "SecurePassword(***)");
+        assert!(!format!("{:?}", password).contains('x'));
+    }
+
+    #[test]
+    fn secure_password_from_string() {
+        let password = SecurePassword::from("hunter2".to_owned());
+
+        assert_eq!(password.expose(), "hunter2");
+    }
+
+    #[cfg(feature = "secrecy")]
+    #[test]
+    fn secure_password_into_secret_string() {
+        use secrecy::{ExposeSecret, SecretString};
+
+        let secret = SecretString::from(SecurePassword::from("hunter2".to_owned()));
+
+        assert_eq!(secret.expose_secret(), "hunter2");
+    }
+
+    #[test]
+    fn generate_n_secure_passwords_assert_count() {
+        let passwords = generate_n_secure_passwords(&Pool::digits(), 15, 10);
+
+        assert_eq!(passwords.len(), 10);
+        assert!(passwords.iter().all(|p| p.expose().len() == 15));
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool contains no elements!")]
+    fn generate_n_secure_passwords_passed_empty_pool() {
+        generate_n_secure_passwords(&Pool::new(), 15, 10);
+    }
+}
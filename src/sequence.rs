@@ -0,0 +1,132 @@
+use crate::keyboard::KeyboardLayout;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Ways two consecutive chars can continue a sequence
+#[derive(Clone, Copy)]
+enum Step {
+    Ascending,
+    Descending,
+    RowForward,
+    RowBackward,
+}
+
+impl Step {
+    const ALL: [Step; 4] = [
+        Step::Ascending,
+        Step::Descending,
+        Step::RowForward,
+        Step::RowBackward,
+    ];
+
+    /// Returns true if `next` follows `previous` in this direction, ignoring ASCII case
+    fn follows(self, previous: char, next: char) -> bool {
+        let (previous, next) = (previous.to_ascii_lowercase(), next.to_ascii_lowercase());
+        match self {
+            Step::Ascending => next as u32 == previous as u32 + 1,
+            Step::Descending => previous as u32 == next as u32 + 1,
+            Step::RowForward => row_adjacent(previous, next),
+            Step::RowBackward => row_adjacent(next, previous),
+        }
+    }
+}
+
+/// Returns true if `right` is the key right of `left` on an unshifted row of
+/// [`KeyboardLayout::QWERTY`]
+fn row_adjacent(left: char, right: char) -> bool {
+    KeyboardLayout::QWERTY.rows().iter().any(|row| {
+        let mut keys = row.keys.chars().skip_while(|key| *key != left);
+        keys.next().is_some() && keys.next() == Some(right)
+    })
+}
+
+/// Find the first run of at least `min_run` chars forming a sequence: consecutive code points
+/// in ascending or descending order like `abcd` or `4321`, or neighbouring keys along a row
+/// of [`KeyboardLayout::QWERTY`] in either direction like `qwer` or `lkjh`. ASCII letters are
+/// compared case-insensitively.
+///
+/// Returns the byte range of the longest run, in any direction, among those starting first,
+/// so it can slice `password`, or `None` if there is none. With `min_run` below 2 any char is
+/// a run.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::contains_sequence;
+/// let password = "x9QWERty";
+///
+/// assert_eq!(contains_sequence(password, 4), Some(2..8));
+/// assert_eq!(&password[2..8], "QWERty");
+/// assert_eq!(contains_sequence("a1b2c3d4", 3), None);
+/// ```
+pub fn contains_sequence(password: &str, min_run: usize) -> Option<Range<usize>> {
+    let chars: Vec<(usize, char)> = password.char_indices().collect();
+    let end_of = |idx: usize| chars.get(idx).map_or(password.len(), |(offset, _)| *offset);
+
+    // A run found at the earliest start can't extend to the left, or it would start earlier
+    for start in 0..chars.len() {
+        let end = Step::ALL
+            .into_iter()
+            .map(|step| {
+                let mut end = start + 1;
+                while end < chars.len() && step.follows(chars[end - 1].1, chars[end].1) {
+                    end += 1;
+                }
+                end
+            })
+            .max()
+            .unwrap();
+        if end - start >= min_run {
+            return Some(chars[start].0..end_of(end));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_sequence_code_points() {
+        assert_eq!(contains_sequence("abcd", 4), Some(0..4));
+        assert_eq!(contains_sequence("x1234y", 3), Some(1..5));
+        assert_eq!(contains_sequence("pw9876", 4), Some(2..6));
+        assert_eq!(contains_sequence("AbCd", 4), Some(0..4));
+        assert_eq!(contains_sequence("abc", 4), None);
+        assert_eq!(contains_sequence("abdc", 3), None);
+    }
+
+    #[test]
+    fn contains_sequence_keyboard_rows() {
+        assert_eq!(contains_sequence("!qwer!", 4), Some(1..5));
+        assert_eq!(contains_sequence("7LKJH", 4), Some(1..5));
+        assert_eq!(contains_sequence("zxcvbnm", 4), Some(0..7));
+        assert_eq!(contains_sequence("a7890b", 4), Some(1..5));
+        assert_eq!(contains_sequence("kl;'", 4), Some(0..4));
+        assert_eq!(contains_sequence("qweasd", 4), None);
+    }
+
+    #[test]
+    fn contains_sequence_reports_first_run() {
+        assert_eq!(contains_sequence("xyzA-3210", 3), Some(0..3));
+        assert_eq!(contains_sequence("1abcdef", 3), Some(1..7));
+        assert_eq!(contains_sequence("é-fghij", 3), Some(3..8));
+    }
+
+    #[test]
+    fn contains_sequence_reports_longest_overlapping_run() {
+        assert_eq!(contains_sequence("fghjk", 3), Some(0..5));
+        assert_eq!(contains_sequence("hijkl", 3), Some(0..5));
+        assert_eq!(contains_sequence("dfghi", 3), Some(0..4));
+        assert_eq!(contains_sequence("x3456yu", 2), Some(1..5));
+    }
+
+    #[test]
+    fn contains_sequence_short_runs() {
+        assert_eq!(contains_sequence("", 3), None);
+        assert_eq!(contains_sequence("éa", 1), Some(0..2));
+        assert_eq!(contains_sequence("ab", 2), Some(0..2));
+        assert_eq!(contains_sequence("k9!Vz", 2), None);
+    }
+}
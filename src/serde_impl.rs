@@ -0,0 +1,102 @@
+use crate::Pool;
+use core::fmt;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialized as a string of its chars, in order
+impl Serialize for Pool {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserialized from a string like [`FromStr`](core::str::FromStr): duplicate chars are
+/// ignored and an empty string gives an empty pool
+impl<'de> Deserialize<'de> for Pool {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(PoolVisitor)
+    }
+}
+
+struct PoolVisitor;
+
+impl Visitor<'_> for PoolVisitor {
+    type Value = Pool;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string of chars")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Pool, E> {
+        s.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_serialize_json() {
+        let pool: Pool = "cba".parse().unwrap();
+
+        assert_eq!(serde_json::to_string(&pool).unwrap(), r#""cba""#);
+    }
+
+    #[test]
+    fn pool_deserialize_json() {
+        let pool: Pool = serde_json::from_str(r#""cba""#).unwrap();
+
+        assert_eq!(pool.to_string(), "cba");
+    }
+
+    #[test]
+    fn pool_json_round_trip_preserves_order() {
+        let pool = Pool::alphanumeric().union(&Pool::ascii_symbols());
+        let json = serde_json::to_string(&pool).unwrap();
+        let other: Pool = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.to_string(), pool.to_string());
+    }
+
+    #[test]
+    fn pool_deserialize_json_invalid_type() {
+        assert!(serde_json::from_str::<Pool>("42").is_err());
+    }
+
+    #[test]
+    fn pool_deserialize_json_empty_string() {
+        let pool: Pool = serde_json::from_str(r#""""#).unwrap();
+
+        assert!(pool.is_empty());
+        assert_eq!(serde_json::to_string(&Pool::new()).unwrap(), r#""""#);
+    }
+
+    #[test]
+    fn pool_deserialize_json_deduplicates() {
+        let pool: Pool = serde_json::from_str(r#""abcabc""#).unwrap();
+
+        assert_eq!(pool.to_string(), "abc");
+    }
+
+    #[test]
+    fn pool_value_round_trip() {
+        let pool: Pool = "z\"é😀".parse().unwrap();
+        let value = serde_json::to_value(&pool).unwrap();
+
+        assert_eq!(value, serde_json::Value::String("z\"é😀".to_owned()));
+        assert_eq!(
+            serde_json::from_value::<Pool>(value).unwrap().to_string(),
+            pool.to_string()
+        );
+    }
+
+    #[test]
+    fn pool_in_settings_struct() {
+        let json = r#"{"length":16,"pool":"0123456789"}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let pool: Pool = serde_json::from_value(value["pool"].clone()).unwrap();
+
+        assert_eq!(pool, Pool::digits());
+    }
+}
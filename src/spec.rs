@@ -0,0 +1,566 @@
+use crate::Pool;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Errors returned by [`Pool::from_spec`]. Offsets are byte offsets in the spec.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SpecError {
+    /// A `-` is not between two chars or two bracket groups
+    DanglingHyphen {
+        /// Offset of the `-`
+        offset: usize,
+    },
+    /// A range ends before it starts, like `z-a`
+    ReversedRange {
+        /// Offset of the first char of the range
+        offset: usize,
+        /// First char of the range
+        start: char,
+        /// Last char of the range
+        end: char,
+    },
+    /// The spec ends with a backslash which escapes nothing
+    TrailingBackslash {
+        /// Offset of the backslash
+        offset: usize,
+    },
+    /// A `[` is never closed
+    UnclosedBracket {
+        /// Offset of the `[`
+        offset: usize,
+    },
+    /// A `]` closes no `[`
+    UnmatchedBracket {
+        /// Offset of the `]`
+        offset: usize,
+    },
+    /// A negated or subtracted bracket group removes every char
+    EmptyResult {
+        /// Offset of the `[` of the group
+        offset: usize,
+    },
+}
+
+impl SpecError {
+    /// Byte offset in the spec where the error was found
+    pub fn offset(&self) -> usize {
+        match self {
+            SpecError::DanglingHyphen { offset }
+            | SpecError::ReversedRange { offset, .. }
+            | SpecError::TrailingBackslash { offset }
+            | SpecError::UnclosedBracket { offset }
+            | SpecError::UnmatchedBracket { offset }
+            | SpecError::EmptyResult { offset } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::DanglingHyphen { offset } => {
+                write!(f, "Dangling '-' at offset {}!", offset)
+            }
+            SpecError::ReversedRange { offset, start, end } => write!(
+                f,
+                "Reversed range '{}-{}' at offset {}!",
+                start, end, offset
+            ),
+            SpecError::TrailingBackslash { offset } => {
+                write!(f, "Trailing backslash at offset {}!", offset)
+            }
+            SpecError::UnclosedBracket { offset } => {
+                write!(f, "Unclosed '[' at offset {}!", offset)
+            }
+            SpecError::UnmatchedBracket { offset } => {
+                write!(f, "Unmatched ']' at offset {}!", offset)
+            }
+            SpecError::EmptyResult { offset } => {
+                write!(f, "Group at offset {} removes every char!", offset)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpecError {}
+
+impl Pool {
+    /// Create pool from a compact spec such as `a-zA-Z0-9_\-`.
+    ///
+    /// - `x-y` adds every char from `x` to `y` included, see [`Pool::from_range`]
+    /// - `\` followed by any char adds that char literally, so `\-` is a hyphen and `\\` a
+    ///   backslash
+    /// - `[...]` groups chars and ranges, and `[^...]` adds every char of the base pool except
+    ///   the listed ones. The base is [`Pool::ascii_printable`], see
+    ///   [`Pool::from_spec_with_base`] to change it
+    /// - `[...]-[...]` adds the chars of the first group which are not in the second one
+    /// - every other char is added as is
+    ///
+    /// Unlike [`FromStr`](core::str::FromStr), which takes every char literally, `-`, `[`
+    /// and `]` must be escaped unless they have one of the meanings above. A `^` is only
+    /// special right after a `[`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let pool = Pool::from_spec(r"a-zA-Z0-9_\-").unwrap();
+    ///
+    /// assert_eq!(pool.len(), 64);
+    /// assert!(pool.contains('-'));
+    ///
+    /// let pool = Pool::from_spec("[a-zA-Z0-9]-[l1IO0]").unwrap();
+    ///
+    /// assert_eq!(pool.len(), 57);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`SpecError`] locating the first malformed part of the spec.
+    pub fn from_spec(spec: &str) -> Result<Pool, SpecError> {
+        Pool::from_spec_with_base(&Pool::ascii_printable(), spec)
+    }
+
+    /// Create pool from a spec like [`Pool::from_spec`], where `[^...]` removes the listed
+    /// chars from `base`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::{Pool, SpecError};
+    /// let pool = Pool::from_spec_with_base(&Pool::digits(), "[^0-4]").unwrap();
+    ///
+    /// assert_eq!(pool.to_string(), "56789");
+    /// assert_eq!(
+    ///     Pool::from_spec_with_base(&Pool::digits(), "[^0-9]"),
+    ///     Err(SpecError::EmptyResult { offset: 0 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`SpecError`] locating the first malformed part of the spec.
+    pub fn from_spec_with_base(base: &Pool, spec: &str) -> Result<Pool, SpecError> {
+        let tokens = tokenize(spec)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos: 0,
+            base,
+        };
+
+        parser.spec()
+    }
+
+    /// Returns the shortest spec accepted by [`Pool::from_spec`] which gives back this pool,
+    /// with the chars in the same order.
+    ///
+    /// Runs of 3 or more chars with consecutive code points, in the order of the pool, are
+    /// written as ranges and `-`, `\`, `[` and `]` are escaped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Pool;
+    /// let mut pool = Pool::alphanumeric();
+    /// pool.extend_from_string("_-");
+    ///
+    /// assert_eq!(pool.to_spec(), r"a-zA-Z0-9_\-");
+    /// assert_eq!(Pool::from_spec(&pool.to_spec()).unwrap(), pool);
+    /// ```
+    pub fn to_spec(&self) -> String {
+        let mut spec = String::new();
+        let chars: Vec<char> = self.iter().copied().collect();
+
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = start;
+            while end + 1 < chars.len() && u32::from(chars[end + 1]) == u32::from(chars[end]) + 1 {
+                end += 1;
+            }
+
+            if end - start >= 2 {
+                push_escaped(&mut spec, chars[start]);
+                spec.push('-');
+                push_escaped(&mut spec, chars[end]);
+            } else {
+                chars[start..=end]
+                    .iter()
+                    .for_each(|ch| push_escaped(&mut spec, *ch));
+            }
+            start = end + 1;
+        }
+
+        spec
+    }
+}
+
+fn push_escaped(spec: &mut String, ch: char) {
+    if matches!(ch, '-' | '\\' | '[' | ']') {
+        spec.push('\\');
+    }
+    spec.push(ch);
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    /// Literal or escaped char, with its offset
+    Char(char, usize),
+    /// Unescaped `-`, with its offset
+    Hyphen(usize),
+    /// `[` opening a group, with its offset
+    Open(usize),
+    /// `^` right after a `[`
+    Caret,
+    /// `]` closing a group
+    Close,
+}
+
+fn tokenize(spec: &str) -> Result<Vec<Token>, SpecError> {
+    let mut tokens = Vec::new();
+    let mut group = None;
+    let mut chars = spec.char_indices();
+    while let Some((offset, ch)) = chars.next() {
+        let token = match ch {
+            '\\' => match chars.next() {
+                Some((_, escaped)) => Token::Char(escaped, offset),
+                None => return Err(SpecError::TrailingBackslash { offset }),
+            },
+            '-' => Token::Hyphen(offset),
+            '[' if group.is_none() => {
+                group = Some(offset);
+                Token::Open(offset)
+            }
+            ']' if group.is_some() => {
+                group = None;
+                Token::Close
+            }
+            ']' => return Err(SpecError::UnmatchedBracket { offset }),
+            '^' if matches!(tokens.last(), Some(Token::Open(_))) => Token::Caret,
+            ch => Token::Char(ch, offset),
+        };
+        tokens.push(token);
+    }
+    if let Some(offset) = group {
+        return Err(SpecError::UnclosedBracket { offset });
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    base: &'a Pool,
+}
+
+impl Parser<'_> {
+    /// Whole spec: chars, ranges and groups
+    fn spec(&mut self) -> Result<Pool, SpecError> {
+        let mut pool = Pool::new();
+        while let Some(token) = self.tokens.get(self.pos) {
+            match token {
+                Token::Open(offset) => {
+                    let offset = *offset;
+                    let mut group = self.group()?;
+                    while let [Token::Hyphen(_), Token::Open(_), ..] = self.tokens[self.pos..] {
+                        self.pos += 1;
+                        group = group.difference(&self.group()?);
+                        if group.is_empty() {
+                            return Err(SpecError::EmptyResult { offset });
+                        }
+                    }
+                    pool.extend(group.iter().copied());
+                }
+                _ => self.item(&mut pool)?,
+            }
+        }
+
+        Ok(pool)
+    }
+
+    /// Bracket group, starting at its `[` and ending after its `]`
+    fn group(&mut self) -> Result<Pool, SpecError> {
+        let offset = match self.tokens[self.pos] {
+            Token::Open(offset) => offset,
+            _ => unreachable!(),
+        };
+        self.pos += 1;
+        let negated = matches!(self.tokens[self.pos], Token::Caret);
+        if negated {
+            self.pos += 1;
+        }
+
+        let mut pool = Pool::new();
+        while !matches!(self.tokens[self.pos], Token::Close) {
+            self.item(&mut pool)?;
+        }
+        self.pos += 1;
+
+        if negated {
+            pool = self.base.difference(&pool);
+            if pool.is_empty() {
+                return Err(SpecError::EmptyResult { offset });
+            }
+        }
+
+        Ok(pool)
+    }
+
+    /// Single char or range, added to `pool`
+    fn item(&mut self, pool: &mut Pool) -> Result<(), SpecError> {
+        match self.tokens[self.pos..] {
+            [Token::Char(start, offset), Token::Hyphen(_), Token::Char(end, _), ..] => {
+                if start > end {
+                    return Err(SpecError::ReversedRange { offset, start, end });
+                }
+                pool.extend_from_range(start..=end);
+                self.pos += 3;
+            }
+            [Token::Char(_, _), Token::Hyphen(offset), ..] | [Token::Hyphen(offset), ..] => {
+                return Err(SpecError::DanglingHyphen { offset });
+            }
+            [Token::Char(ch, _), ..] => {
+                pool.insert(ch);
+                self.pos += 1;
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn from_spec_ranges() {
+        let pool = Pool::from_spec("a-zA-Z0-9").unwrap();
+
+        assert_eq!(pool, Pool::alphanumeric());
+        assert_eq!(pool.to_string(), Pool::alphanumeric().to_string());
+    }
+
+    #[test]
+    fn from_spec_literals_and_escapes() {
+        let pool = Pool::from_spec(r"_\-\\x").unwrap();
+
+        assert_eq!(pool.to_string(), r"_-\x");
+    }
+
+    #[test]
+    fn from_spec_escaped_range_ends() {
+        let pool = Pool::from_spec(r"\--/").unwrap();
+
+        assert_eq!(pool.to_string(), "-./");
+    }
+
+    #[test]
+    fn from_spec_single_char_range() {
+        assert_eq!(Pool::from_spec("a-a").unwrap().to_string(), "a");
+    }
+
+    #[test]
+    fn from_spec_multibyte() {
+        let pool = Pool::from_spec("α-ωé").unwrap();
+
+        assert_eq!(pool.len(), 26);
+        assert!(pool.contains('é'));
+    }
+
+    #[test]
+    fn from_spec_round_trip() {
+        let cases = [
+            ("a-z", Pool::lowercase()),
+            ("A-Z", Pool::uppercase()),
+            ("0-9", Pool::digits()),
+            ("a-zA-Z0-9", Pool::alphanumeric()),
+            (" -~", Pool::ascii_printable()),
+        ];
+
+        for (spec, pool) in cases {
+            assert_eq!(Pool::from_spec(spec).unwrap().to_string(), pool.to_string());
+        }
+    }
+
+    #[test]
+    fn from_spec_empty() {
+        assert!(Pool::from_spec("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn from_spec_dangling_hyphen() {
+        assert_eq!(
+            Pool::from_spec("-a"),
+            Err(SpecError::DanglingHyphen { offset: 0 })
+        );
+        assert_eq!(
+            Pool::from_spec("a-"),
+            Err(SpecError::DanglingHyphen { offset: 1 })
+        );
+        assert_eq!(
+            Pool::from_spec("a-c-e"),
+            Err(SpecError::DanglingHyphen { offset: 3 })
+        );
+        assert_eq!(
+            Pool::from_spec("é-"),
+            Err(SpecError::DanglingHyphen { offset: 2 })
+        );
+    }
+
+    #[test]
+    fn from_spec_reversed_range() {
+        let err = Pool::from_spec("a-z9-0").unwrap_err();
+
+        assert_eq!(
+            err,
+            SpecError::ReversedRange {
+                offset: 3,
+                start: '9',
+                end: '0'
+            }
+        );
+        assert_eq!(err.offset(), 3);
+        assert_eq!(err.to_string(), "Reversed range '9-0' at offset 3!");
+    }
+
+    #[test]
+    fn from_spec_trailing_backslash() {
+        assert_eq!(
+            Pool::from_spec(r"ab\"),
+            Err(SpecError::TrailingBackslash { offset: 2 })
+        );
+        assert_eq!(Pool::from_spec(r"ab\\").unwrap().to_string(), r"ab\");
+    }
+
+    #[test]
+    fn from_spec_group() {
+        assert_eq!(Pool::from_spec("[a-c]x").unwrap().to_string(), "abcx");
+    }
+
+    #[test]
+    fn from_spec_negated_group() {
+        let pool = Pool::from_spec("[^a-z]").unwrap();
+
+        assert_eq!(pool.len(), 95 - 26);
+        assert!(pool.contains(' '));
+        assert!(!pool.contains('q'));
+    }
+
+    #[test]
+    fn from_spec_with_base_negated_group() {
+        let pool = Pool::from_spec_with_base(&Pool::alphanumeric(), "[^l1IO0]").unwrap();
+
+        assert_eq!(pool.len(), 57);
+        assert!("l1IO0".chars().all(|ch| !pool.contains(ch)));
+    }
+
+    #[test]
+    fn from_spec_group_difference() {
+        let pool = Pool::from_spec("[a-zA-Z0-9]-[l1IO0]-[xyz]").unwrap();
+
+        assert_eq!(pool.len(), 54);
+        assert!("l1IO0xyz".chars().all(|ch| !pool.contains(ch)));
+    }
+
+    #[test]
+    fn from_spec_escaped_caret_is_literal() {
+        assert_eq!(Pool::from_spec(r"[\^a]").unwrap().to_string(), "^a");
+        assert_eq!(Pool::from_spec("[a^]").unwrap().to_string(), "a^");
+        assert_eq!(Pool::from_spec("^").unwrap().to_string(), "^");
+    }
+
+    #[test]
+    fn from_spec_escaped_brackets_are_literal() {
+        assert_eq!(Pool::from_spec(r"\[\]").unwrap().to_string(), "[]");
+        assert_eq!(Pool::from_spec(r"[[\]]").unwrap().to_string(), "[]");
+    }
+
+    #[test]
+    fn from_spec_empty_result() {
+        let base: Pool = "ab".parse().unwrap();
+
+        assert_eq!(
+            Pool::from_spec_with_base(&base, "x[^ab]"),
+            Err(SpecError::EmptyResult { offset: 1 })
+        );
+        assert_eq!(
+            Pool::from_spec("[ab]-[a-c]"),
+            Err(SpecError::EmptyResult { offset: 0 })
+        );
+    }
+
+    #[test]
+    fn from_spec_bracket_errors() {
+        assert_eq!(
+            Pool::from_spec("ab[cd"),
+            Err(SpecError::UnclosedBracket { offset: 2 })
+        );
+        assert_eq!(
+            Pool::from_spec("ab]"),
+            Err(SpecError::UnmatchedBracket { offset: 2 })
+        );
+        assert_eq!(
+            Pool::from_spec("[a-]"),
+            Err(SpecError::DanglingHyphen { offset: 2 })
+        );
+        assert_eq!(
+            Pool::from_spec("[a]-b"),
+            Err(SpecError::DanglingHyphen { offset: 3 })
+        );
+    }
+
+    #[test]
+    fn to_spec_ranges_and_literals() {
+        let cases = [
+            ("", ""),
+            ("abc", "a-c"),
+            ("ab", "ab"),
+            ("abd", "abd"),
+            ("xabcy", "xa-cy"),
+            ("cba", "cba"),
+            (r"-\[]^", r"\-\\\[\]^"),
+            (",-./", r",-/"),
+            ("αβγδ€", "α-δ€"),
+        ];
+
+        for (chars, spec) in cases {
+            let pool: Pool = chars.parse().unwrap();
+
+            assert_eq!(pool.to_spec(), spec);
+            assert_eq!(Pool::from_spec(spec).unwrap().to_string(), chars);
+        }
+    }
+
+    #[test]
+    fn to_spec_presets() {
+        assert_eq!(Pool::alphanumeric().to_spec(), "a-zA-Z0-9");
+        assert_eq!(Pool::ascii_printable().to_spec(), " -~");
+        assert_eq!(Pool::from_spec(&Pool::full().to_spec()), Ok(Pool::full()));
+    }
+
+    #[test]
+    fn to_spec_round_trip_random_pools() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..500 {
+            let mut pool = Pool::new();
+            for _ in 0..rng.gen_range(0..8) {
+                let start = rng.gen_range(0x20..0x3000);
+                let end = start + rng.gen_range(0..40);
+                pool.extend_from_range(
+                    char::from_u32(start).unwrap()..=char::from_u32(end).unwrap(),
+                );
+                pool.insert(rng.gen_range(' '..='~'));
+            }
+            if rng.gen_bool(0.5) {
+                pool.shuffle(&mut rng);
+            }
+
+            let round_trip = Pool::from_spec(&pool.to_spec()).unwrap();
+
+            assert_eq!(round_trip.to_string(), pool.to_string());
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{calculate_entropy, Pool};
+use std::fmt;
+use std::time::Duration;
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+const YEAR: f64 = 365.25 * DAY;
+
+/// Estimates the average time needed to find a password of `entropy` bits by brute force,
+/// trying `guesses_per_second` candidates per second. On average half of the `2^entropy`
+/// candidates are tried before finding the password.
+///
+/// Times too long for a [`Duration`] saturate to [`Duration::MAX`], as does a non-positive
+/// or NaN `guesses_per_second`. A negative or NaN `entropy` gives [`Duration::ZERO`].
+///
+/// # Examples
+/// ```
+/// # use libpassgen::estimate_crack_time;
+/// use std::time::Duration;
+///
+/// assert_eq!(estimate_crack_time(11.0, 1024.0), Duration::from_secs(1));
+/// assert_eq!(estimate_crack_time(256.0, 1e12), Duration::MAX);
+/// ```
+pub fn estimate_crack_time(entropy: f64, guesses_per_second: f64) -> Duration {
+    if entropy.is_nan() || entropy < 0.0 {
+        return Duration::ZERO;
+    }
+    if guesses_per_second.is_nan() || guesses_per_second <= 0.0 {
+        return Duration::MAX;
+    }
+
+    let seconds = (entropy - 1.0).exp2() / guesses_per_second;
+
+    Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
+}
+
+/// Formats a crack time as a short human readable string, like `"3.2 years"`, using the
+/// largest unit which gives at least 1. Years are Julian years of 365.25 days, and more than a
+/// million years are written in scientific notation.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{estimate_crack_time, format_crack_time};
+/// use std::time::Duration;
+///
+/// assert_eq!(format_crack_time(Duration::from_secs(90)), "1.5 minutes");
+/// assert_eq!(format_crack_time(estimate_crack_time(64.0, 1e10)), "29.2 years");
+/// ```
+pub fn format_crack_time(time: Duration) -> String {
+    let seconds = time.as_secs_f64();
+    let (value, unit) = match seconds {
+        s if s < 1.0 => return "less than a second".to_owned(),
+        s if s < MINUTE => (s, "seconds"),
+        s if s < HOUR => (s / MINUTE, "minutes"),
+        s if s < DAY => (s / HOUR, "hours"),
+        s if s < YEAR => (s / DAY, "days"),
+        s if s < 1e6 * YEAR => (s / YEAR, "years"),
+        s => return format!("{:.1e} years", s / YEAR),
+    };
+
+    format!("{:.1} {}", value, unit)
+}
+
+/// Strength of a password, from its entropy in bits
+///
+/// | Strength     | Entropy         |
+/// |--------------|-----------------|
+/// | `VeryWeak`   | less than 28    |
+/// | `Weak`       | 28 to 35.99     |
+/// | `Reasonable` | 36 to 59.99     |
+/// | `Strong`     | 60 to 127.99    |
+/// | `VeryStrong` | 128 or more     |
+///
+/// Variants are ordered from the weakest to the strongest.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Strength {
+    /// Less than 28 bits
+    VeryWeak,
+    /// From 28 bits
+    Weak,
+    /// From 36 bits
+    Reasonable,
+    /// From 60 bits
+    Strong,
+    /// From 128 bits
+    VeryStrong,
+}
+
+impl Strength {
+    /// Returns the strength of a password of `entropy` bits
+    ///
+    /// # Examples
+    /// ```
+    /// # use libpassgen::Strength;
+    /// assert_eq!(Strength::from_entropy(64.0), Strength::Strong);
+    /// ```
+    pub fn from_entropy(entropy: f64) -> Strength {
+        match entropy {
+            e if e >= 128.0 => Strength::VeryStrong,
+            e if e >= 60.0 => Strength::Strong,
+            e if e >= 36.0 => Strength::Reasonable,
+            e if e >= 28.0 => Strength::Weak,
+            _ => Strength::VeryWeak,
+        }
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Strength::VeryWeak => "very weak",
+            Strength::Weak => "weak",
+            Strength::Reasonable => "reasonable",
+            Strength::Strong => "strong",
+            Strength::VeryStrong => "very strong",
+        };
+
+        f.write_str(label)
+    }
+}
+
+/// Entropy of a random password and its [`Strength`], returned by [`entropy_report`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyReport {
+    /// Number of chars of the password
+    pub length: usize,
+    /// Number of chars of the pool
+    pub pool_size: usize,
+    /// Entropy in bits, `length * log2(pool_size)`
+    pub bits: f64,
+    /// Classification of `bits`
+    pub strength: Strength,
+}
+
+impl fmt::Display for EntropyReport {
+    /// Writes one line per field, with the formula used for the entropy:
+    ///
+    /// ```text
+    /// Length: 16 chars
+    /// Pool size: 64 chars
+    /// Entropy: 96.0 bits (16 * log2(64))
+    /// Strength: strong
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Length: {} chars", self.length)?;
+        writeln!(f, "Pool size: {} chars", self.pool_size)?;
+        writeln!(
+            f,
+            "Entropy: {:.1} bits ({} * log2({}))",
+            self.bits, self.length, self.pool_size
+        )?;
+        write!(f, "Strength: {}", self.strength)
+    }
+}
+
+/// Calculates the entropy of a random password of `length` chars picked from a pool of
+/// `pool_size` chars, and classifies it.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{entropy_report, Strength};
+/// let report = entropy_report(16, 64);
+///
+/// assert_eq!(report.bits, 96.0);
+/// assert_eq!(report.strength, Strength::Strong);
+/// println!("{}", report);
+/// ```
+pub fn entropy_report(length: usize, pool_size: usize) -> EntropyReport {
+    let bits = calculate_entropy(length, pool_size);
+
+    EntropyReport {
+        length,
+        pool_size,
+        bits,
+        strength: Strength::from_entropy(bits),
+    }
+}
+
+/// Classifies `password` as if each of its chars had been picked at random from `pool`.
+///
+/// Chars of `password` which are not in `pool` are added to the alphabet, so a wrong pool
+/// can't make the password look weaker than that. The result is an upper bound: a password
+/// chosen by a human has far less entropy than a random one.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, Strength, classify_strength};
+/// assert_eq!(classify_strength("8531", &Pool::digits()), Strength::VeryWeak);
+/// assert_eq!(classify_strength("x7Gp2mQa9vLz", &Pool::alphanumeric()), Strength::Strong);
+/// ```
+pub fn classify_strength(password: &str, pool: &Pool) -> Strength {
+    let alphabet = pool.union(&password.chars().collect()).len();
+
+    Strength::from_entropy(calculate_entropy(password.chars().count(), alphabet))
+}
+
+/// Classifies `password` like [`classify_strength`], inferring the alphabet from the classes
+/// of its chars: 26 if it has a lowercase ASCII letter, 26 for an uppercase one, 10 for a
+/// digit, 33 for an ASCII symbol or space, plus 1 for each distinct other char.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Strength, classify_strength_auto};
+/// assert_eq!(classify_strength_auto("password"), Strength::Reasonable);
+/// assert_eq!(classify_strength_auto("correct horse battery staple"), Strength::VeryStrong);
+/// ```
+pub fn classify_strength_auto(password: &str) -> Strength {
+    let has = |class: fn(&char) -> bool| password.chars().any(|ch| class(&ch));
+    let others: Pool = password.chars().filter(|ch| !ch.is_ascii()).collect();
+    let alphabet = [
+        (has(char::is_ascii_lowercase), 26),
+        (has(char::is_ascii_uppercase), 26),
+        (has(char::is_ascii_digit), 10),
+        (has(|ch| ch.is_ascii_punctuation() || *ch == ' '), 33),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, size)| size)
+    .sum::<usize>()
+        + others.len();
+
+    Strength::from_entropy(calculate_entropy(password.chars().count(), alphabet))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_crack_time_half_keyspace() {
+        assert_eq!(estimate_crack_time(1.0, 1.0), Duration::from_secs(1));
+        assert_eq!(estimate_crack_time(21.0, 1.0), Duration::from_secs(1 << 20));
+        assert_eq!(estimate_crack_time(0.0, 2.0), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn estimate_crack_time_saturates() {
+        assert_eq!(estimate_crack_time(128.0, 1e9), Duration::MAX);
+        assert_eq!(estimate_crack_time(f64::INFINITY, 1e9), Duration::MAX);
+        assert_eq!(estimate_crack_time(40.0, 0.0), Duration::MAX);
+        assert_eq!(estimate_crack_time(40.0, f64::NAN), Duration::MAX);
+        assert_eq!(estimate_crack_time(-1.0, 1e9), Duration::ZERO);
+        assert_eq!(estimate_crack_time(f64::NAN, 1e9), Duration::ZERO);
+    }
+
+    #[test]
+    fn format_crack_time_units() {
+        let cases = [
+            (Duration::from_millis(500), "less than a second"),
+            (Duration::from_secs(5), "5.0 seconds"),
+            (Duration::from_secs(150), "2.5 minutes"),
+            (Duration::from_secs(3 * 3600), "3.0 hours"),
+            (Duration::from_secs(36 * 3600), "1.5 days"),
+            (Duration::from_secs_f64(3.2 * YEAR), "3.2 years"),
+            (Duration::from_secs_f64(2.5e9 * YEAR), "2.5e9 years"),
+        ];
+
+        for (time, expected) in cases {
+            assert_eq!(format_crack_time(time), expected);
+        }
+    }
+
+    #[test]
+    fn format_crack_time_max() {
+        assert!(format_crack_time(Duration::MAX).ends_with("e11 years"));
+    }
+
+    #[test]
+    fn strength_from_entropy_thresholds() {
+        assert_eq!(Strength::from_entropy(0.0), Strength::VeryWeak);
+        assert_eq!(Strength::from_entropy(27.9), Strength::VeryWeak);
+        assert_eq!(Strength::from_entropy(28.0), Strength::Weak);
+        assert_eq!(Strength::from_entropy(36.0), Strength::Reasonable);
+        assert_eq!(Strength::from_entropy(60.0), Strength::Strong);
+        assert_eq!(Strength::from_entropy(128.0), Strength::VeryStrong);
+        assert_eq!(Strength::from_entropy(f64::NAN), Strength::VeryWeak);
+        assert!(Strength::Weak < Strength::Strong);
+    }
+
+    #[test]
+    fn classify_strength_uses_pool_size() {
+        // 10 * log2(64) = 60 bits
+        let pool: Pool = Pool::alphanumeric().union(&"+/".parse().unwrap());
+
+        assert_eq!(classify_strength("abcdefghij", &pool), Strength::Strong);
+        assert_eq!(classify_strength("abcdefghi", &pool), Strength::Reasonable);
+        assert_eq!(classify_strength("", &pool), Strength::VeryWeak);
+    }
+
+    #[test]
+    fn classify_strength_adds_missing_chars() {
+        let pool: Pool = "a".parse().unwrap();
+
+        assert_eq!(
+            classify_strength("abcdefghij", &pool),
+            classify_strength("abcdefghij", &"abcdefghij".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn classify_strength_auto_classes() {
+        // 8 * log2(26) = 37.6 bits
+        assert_eq!(classify_strength_auto("abcdefgh"), Strength::Reasonable);
+        // 8 * log2(10) = 26.6 bits
+        assert_eq!(classify_strength_auto("12345678"), Strength::VeryWeak);
+        // 8 * log2(95) = 52.6 bits
+        assert_eq!(classify_strength_auto("aB3$efgh"), Strength::Reasonable);
+        // 4 * log2(26 + 2) = 19.2 bits
+        assert_eq!(classify_strength_auto("aéèa"), Strength::VeryWeak);
+    }
+
+    #[test]
+    fn entropy_report_display() {
+        let report = entropy_report(10, 26);
+
+        assert_eq!(report.strength, Strength::Reasonable);
+        assert_eq!(
+            report.to_string(),
+            "Length: 10 chars\nPool size: 26 chars\nEntropy: 47.0 bits (10 * log2(26))\nStrength: reasonable"
+        );
+    }
+}
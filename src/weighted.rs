@@ -0,0 +1,244 @@
+use crate::requirements::pick;
+use crate::{unwrap_or_empty, validate, PassgenError, Pool};
+use rand::distributions::{Distribution, WeightedIndex};
+
+/// Largest difference from 1 accepted for the sum of the probabilities of
+/// [`try_generate_mixed`], to absorb rounding errors like `0.6 + 0.3 + 0.1`
+const PROBABILITY_EPSILON: f64 = 1e-6;
+
+/// Generate random password where the char at index `i` of `pool` is picked with a
+/// probability proportional to `weights[i]`. A weight of zero means the char is never used.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_weighted};
+/// let pool: Pool = "ab#".parse().unwrap();
+/// let password = generate_weighted(&pool, &[5, 5, 0], 20).unwrap();
+///
+/// assert!(!password.contains('#'));
+/// ```
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `pool` is empty, [`PassgenError::ZeroLength`] if
+/// `length` is zero, [`PassgenError::WeightsMismatch`] if there is not exactly one weight per
+/// char of `pool` and [`PassgenError::InvalidWeights`] if all weights are zero.
+pub fn generate_weighted(
+    pool: &Pool,
+    weights: &[u32],
+    length: usize,
+) -> Result<String, PassgenError> {
+    validate(pool, length)?;
+    if weights.len() != pool.len() {
+        return Err(PassgenError::WeightsMismatch {
+            pool: pool.len(),
+            weights: weights.len(),
+        });
+    }
+
+    // Sum the weights as u64 so that large weights can't overflow
+    let dist = WeightedIndex::new(weights.iter().map(|&weight| u64::from(weight)))
+        .map_err(|_| PassgenError::InvalidWeights)?;
+    let mut rng = rand::thread_rng();
+
+    Ok((0..length)
+        .map(|_| *pool.get(dist.sample(&mut rng)).unwrap())
+        .collect())
+}
+
+/// Generate random password where each char is picked from one of the pools of `classes`,
+/// chosen with its probability, then uniformly within that pool. With
+/// `[(letters, 0.6), (digits, 0.3), (symbols, 0.1)]` about 60% of the chars are letters on
+/// average.
+///
+/// # Examples
+/// ```
+/// # use libpassgen::{Pool, generate_mixed};
+/// let classes = [(Pool::lowercase(), 0.7), (Pool::digits(), 0.3)];
+/// let password = generate_mixed(&classes, 20);
+///
+/// assert_eq!(password.len(), 20);
+/// ```
+///
+/// An empty password is returned when `length` is zero.
+///
+/// # Panics
+/// Panics in the same cases as [`try_generate_mixed`] returns an error, except for a zero
+/// `length`.
+pub fn generate_mixed(classes: &[(Pool, f64)], length: usize) -> String {
+    unwrap_or_empty(try_generate_mixed(classes, length))
+}
+
+/// Generate random password mixing the pools of `classes` with the given probabilities,
+/// returning an error instead of panicking. See [`generate_mixed`].
+///
+/// # Errors
+/// Returns [`PassgenError::EmptyPool`] if `classes` or one of its pools is empty,
+/// [`PassgenError::ZeroLength`] if `length` is zero and
+/// [`PassgenError::InvalidProbabilities`] if a probability is negative or NaN, or if they
+/// don't add up to 1 within `1e-6`.
+pub fn try_generate_mixed(classes: &[(Pool, f64)], length: usize) -> Result<String, PassgenError> {
+    if classes.is_empty() || classes.iter().any(|(pool, _)| pool.is_empty()) {
+        return Err(PassgenError::EmptyPool);
+    }
+    let sum: f64 = classes.iter().map(|(_, probability)| probability).sum();
+    if classes
+        .iter()
+        .any(|(_, probability)| probability.is_nan() || *probability < 0.0)
+        || (sum - 1.0).abs() > PROBABILITY_EPSILON
+    {
+        return Err(PassgenError::InvalidProbabilities);
+    }
+    if length == 0 {
+        return Err(PassgenError::ZeroLength);
+    }
+
+    let dist = WeightedIndex::new(classes.iter().map(|(_, probability)| probability))
+        .map_err(|_| PassgenError::InvalidProbabilities)?;
+    let mut rng = rand::thread_rng();
+
+    Ok((0..length)
+        .map(|_| pick(&classes[dist.sample(&mut rng)].0, &mut rng))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_weighted_assert_len() {
+        let password = generate_weighted(&Pool::digits(), &[1; 10], 15).unwrap();
+
+        assert_eq!(password.len(), 15);
+    }
+
+    #[test]
+    fn generate_weighted_zero_weight_never_used() {
+        let pool: Pool = "abc".parse().unwrap();
+        let password = generate_weighted(&pool, &[1, 0, 1], 1000).unwrap();
+
+        assert!(!password.contains('b'));
+    }
+
+    #[test]
+    fn generate_weighted_single_weight() {
+        let pool: Pool = "abc".parse().unwrap();
+
+        assert_eq!(generate_weighted(&pool, &[0, 0, 3], 5).unwrap(), "ccccc");
+    }
+
+    #[test]
+    fn generate_weighted_follows_weights() {
+        let pool: Pool = "ab".parse().unwrap();
+        let password = generate_weighted(&pool, &[9, 1], 10_000).unwrap();
+        let count = password.chars().filter(|&ch| ch == 'a').count();
+
+        assert!((8500..9500).contains(&count));
+    }
+
+    #[test]
+    fn generate_weighted_weights_mismatch() {
+        let result = generate_weighted(&Pool::digits(), &[1; 9], 15);
+
+        assert_eq!(
+            result,
+            Err(PassgenError::WeightsMismatch {
+                pool: 10,
+                weights: 9
+            })
+        );
+    }
+
+    #[test]
+    fn generate_weighted_all_zero() {
+        let result = generate_weighted(&Pool::digits(), &[0; 10], 15);
+
+        assert_eq!(result, Err(PassgenError::InvalidWeights));
+    }
+
+    #[test]
+    fn generate_weighted_max_weights_do_not_overflow() {
+        let pool: Pool = "ab".parse().unwrap();
+        let password = generate_weighted(&pool, &[u32::MAX, 1], 100).unwrap();
+
+        assert_eq!(password.len(), 100);
+        assert!(password.contains('a'));
+    }
+
+    #[test]
+    fn generate_weighted_empty_pool() {
+        let result = generate_weighted(&Pool::new(), &[], 15);
+
+        assert_eq!(result, Err(PassgenError::EmptyPool));
+    }
+
+    #[test]
+    fn generate_mixed_follows_probabilities() {
+        let classes = [
+            (Pool::lowercase(), 0.6),
+            (Pool::digits(), 0.3),
+            (Pool::ascii_symbols(), 0.1),
+        ];
+        let password = generate_mixed(&classes, 10_000);
+        let count = |class: fn(&char) -> bool| password.chars().filter(class).count();
+
+        assert_eq!(password.len(), 10_000);
+        assert!((5500..6500).contains(&count(char::is_ascii_lowercase)));
+        assert!((2500..3500).contains(&count(char::is_ascii_digit)));
+        assert!((700..1300).contains(&count(char::is_ascii_punctuation)));
+    }
+
+    #[test]
+    fn generate_mixed_zero_probability_never_used() {
+        let classes = [(Pool::digits(), 1.0), (Pool::lowercase(), 0.0)];
+
+        assert!(generate_mixed(&classes, 1000)
+            .chars()
+            .all(|ch| ch.is_ascii_digit()));
+    }
+
+    #[test]
+    fn try_generate_mixed_errors() {
+        let cases = [
+            (vec![], 8, PassgenError::EmptyPool),
+            (vec![(Pool::new(), 1.0)], 8, PassgenError::EmptyPool),
+            (vec![(Pool::digits(), 1.0)], 0, PassgenError::ZeroLength),
+            (
+                vec![(Pool::digits(), 0.5), (Pool::lowercase(), 0.4)],
+                8,
+                PassgenError::InvalidProbabilities,
+            ),
+            (
+                vec![(Pool::digits(), 1.5), (Pool::lowercase(), -0.5)],
+                8,
+                PassgenError::InvalidProbabilities,
+            ),
+            (
+                vec![(Pool::digits(), f64::NAN)],
+                8,
+                PassgenError::InvalidProbabilities,
+            ),
+        ];
+
+        for (classes, length, error) in cases {
+            assert_eq!(try_generate_mixed(&classes, length), Err(error));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Probabilities must be non-negative and add up to 1!")]
+    fn generate_mixed_invalid_probabilities() {
+        generate_mixed(&[(Pool::digits(), 0.5)], 8);
+    }
+
+    #[test]
+    fn generate_mixed_zero_length() {
+        assert_eq!(generate_mixed(&[(Pool::digits(), 1.0)], 0), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "Probabilities must be non-negative and add up to 1!")]
+    fn generate_mixed_zero_length_invalid_probabilities() {
+        generate_mixed(&[(Pool::digits(), 0.5)], 0);
+    }
+}